@@ -0,0 +1,685 @@
+use datetime::{DatePiece, LocalDate};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+use tauri_app_lib::{
+    export, fairness, holiday_cache::HolidayCache, holidays, html, i18n::Locale, ics, layout, pdf,
+    plan, stats, types,
+};
+
+const USAGE: &str = "\
+Usage: waschplan-cli --config <FILE> (--year <YEAR> | --start <DATE> --end <DATE>) --floor <FLOOR> --position <POSITION> --days-left <DAYS> [OPTIONS]
+
+Generates a laundry plan without opening the app window.
+
+Period:
+  --year <YEAR>            Plan the whole year
+  --start <DATE>           First day of the plan (e.g. 1.4.2025), defaults to the start of --year
+  --end <DATE>             Last day of the plan (e.g. 31.12.2026), defaults to the end of --year
+
+Last apartment to wash:
+  --floor <FLOOR>          Floor of the apartment that washed last
+  --position <POSITION>    Left, Middle, Right or the number of the apartment from the left
+  --days-left <DAYS>       Days the apartment has left to wash
+  --state-in <FILE>        Read floor, position and days left from a file written by --state-out
+
+Options:
+  --holidays <FILE>        JSON object mapping dates (e.g. \"1.8.2025\") to holiday names
+  --country <ISO>          Use the public holidays of the given country (e.g. CH)
+  --subdivision <ISO>      Subdivision used together with --country (e.g. CH-ZH)
+  --online-holidays        Download the holidays of --country instead of computing them
+  --check-holidays         Report differences between computed and downloaded holidays
+  --cache-dir <DIR>        Keep downloaded holidays in DIR and use them when offline
+  --refresh-holidays       Download the holidays again even if they are cached
+  --cache-max-age <DAYS>   Download cached holidays again after DAYS days (default 30)
+  --exclude-sunday         Don't assign any apartment on Sundays
+  --exclude-weekdays <DAYS>  Also close the laundry room on these days, e.g. Saturday,Wednesday
+  --holiday-policy <POLICY>  Apartment whose day is a holiday: pause, lose-day, keep or shift
+  --rotation-order <ORDER>  bottom-up, top-down or serpentine (default from the config)
+  --fair                   Balance days, Saturdays and popular months instead of rotating
+  --popular-months <MONTHS>  Months in demand for --fair, e.g. 7,8
+  --slots <LABELS>         Several apartments wash per day, e.g. Vormittag,Nachmittag
+  --locale <LOCALE>        Language of the plan and the holidays: de, fr, it or en (default from the config)
+  --template <FILE>        Template of the HTML plan (default from the config or the built-in layout)
+  --apartment-label <LABEL>  Show apartments by position, label or tenant (default from the config)
+  --hide-names             Leave out tenant names and contact details, e.g. for posted plans
+  --format <FORMAT>        Output format: html (default), pdf, ics, csv, json or stats (days per apartment)
+  --output <FILE>          Write the plan to a file instead of stdout
+  --state-out <FILE>       Write the apartment that washes last in this plan to FILE
+  --help                   Print this message
+
+Calendar export (--format ics):
+  --apartment <FLOOR>:<POSITION>  Only add the laundry days of this apartment
+  --split-apartments       Write one calendar per apartment into the --output directory
+
+Layout (--format html or pdf, defaults from the config):
+  --months-per-row <N>     Months next to each other: 1, 2, 3, 4 or 6
+  --page-split <SPLIT>     Start a new page: continuous, half-year or quarter
+  --orientation <ORIENTATION>  portrait or landscape";
+
+enum HolidaySource {
+    NoHolidays,
+    File(PathBuf),
+    Country {
+        country: String,
+        subdivision: String,
+        source: holidays::HolidaySource,
+    },
+}
+
+enum OutputFormat {
+    Html,
+    Pdf,
+    Ics,
+    Export(export::ExportFormat),
+    Stats,
+}
+
+struct Args {
+    config: PathBuf,
+    start: LocalDate,
+    end: LocalDate,
+    apartment_info: types::ApartmentInfo,
+    holidays: HolidaySource,
+    exclude_sunday: bool,
+    excluded_weekdays: Vec<plan::Weekday>,
+    holiday_policy: Option<types::HolidayPolicy>,
+    slots: Option<Vec<String>>,
+    rotation_order: Option<types::RotationOrder>,
+    fair: bool,
+    popular_months: Option<Vec<u8>>,
+    locale: Option<Locale>,
+    template: Option<String>,
+    apartment_label: Option<types::ApartmentLabel>,
+    hide_names: bool,
+    months_per_row: Option<usize>,
+    page_split: Option<layout::PageSplit>,
+    orientation: Option<layout::Orientation>,
+    check_holidays: bool,
+    cache_dir: Option<PathBuf>,
+    cache_max_age: Option<u64>,
+    refresh_holidays: bool,
+    format: OutputFormat,
+    apartment: Option<types::ApartmentPosition>,
+    split_apartments: bool,
+    output: Option<PathBuf>,
+    state_out: Option<PathBuf>,
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args.next().ok_or(format!("Missing value for {}.", flag))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str, flag: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value '{}' for {}.", value, flag))
+}
+
+fn parse_position(value: &str) -> Result<types::FloorPosition, String> {
+    match value {
+        "Left" => Ok(types::FloorPosition::Left),
+        "Middle" => Ok(types::FloorPosition::Middle),
+        "Right" => Ok(types::FloorPosition::Right),
+        // apartments are counted from the left starting at 1
+        _ => match value.parse::<usize>() {
+            Ok(unit) if unit > 0 => Ok(types::FloorPosition::Unit(unit - 1)),
+            _ => Err(format!("Invalid position '{}'.", value)),
+        },
+    }
+}
+
+fn parse_date(value: &str, flag: &str) -> Result<LocalDate, String> {
+    types::string_to_local_date(value).ok_or(format!("Invalid value '{}' for {}.", value, flag))
+}
+
+// @param value comma separated weekdays, e.g. Saturday,Sunday
+fn parse_weekdays(value: &str) -> Result<Vec<plan::Weekday>, String> {
+    value
+        .split(',')
+        .map(|weekday| match weekday.trim() {
+            "Monday" => Ok(plan::Weekday::Monday),
+            "Tuesday" => Ok(plan::Weekday::Tuesday),
+            "Wednesday" => Ok(plan::Weekday::Wednesday),
+            "Thursday" => Ok(plan::Weekday::Thursday),
+            "Friday" => Ok(plan::Weekday::Friday),
+            "Saturday" => Ok(plan::Weekday::Saturday),
+            "Sunday" => Ok(plan::Weekday::Sunday),
+            _ => Err(format!("Unknown weekday '{}'.", weekday)),
+        })
+        .collect()
+}
+
+// @param value comma separated months from 1 to 12, e.g. 7,8
+fn parse_months(value: &str, flag: &str) -> Result<Vec<u8>, String> {
+    value
+        .split(',')
+        .map(|month| match parse_number(month.trim(), flag)? {
+            month @ 1..=12 => Ok(month),
+            _ => Err(format!("Invalid value '{}' for {}.", month, flag)),
+        })
+        .collect()
+}
+
+fn parse_rotation_order(value: &str) -> Result<types::RotationOrder, String> {
+    match value {
+        "bottom-up" => Ok(types::RotationOrder::BottomUp),
+        "top-down" => Ok(types::RotationOrder::TopDown),
+        "serpentine" => Ok(types::RotationOrder::Serpentine),
+        _ => Err(format!("Unknown rotation order '{}'.", value)),
+    }
+}
+
+fn parse_holiday_policy(value: &str) -> Result<types::HolidayPolicy, String> {
+    match value {
+        "pause" => Ok(types::HolidayPolicy::Pause),
+        "lose-day" => Ok(types::HolidayPolicy::LoseDay),
+        "keep" => Ok(types::HolidayPolicy::Keep),
+        "shift" => Ok(types::HolidayPolicy::Shift),
+        _ => Err(format!("Unknown holiday policy '{}'.", value)),
+    }
+}
+
+fn parse_locale(value: &str) -> Result<Locale, String> {
+    match value {
+        "de" => Ok(Locale::De),
+        "fr" => Ok(Locale::Fr),
+        "it" => Ok(Locale::It),
+        "en" => Ok(Locale::En),
+        _ => Err(format!("Unknown locale '{}'.", value)),
+    }
+}
+
+fn parse_apartment_label(value: &str) -> Result<types::ApartmentLabel, String> {
+    match value {
+        "position" => Ok(types::ApartmentLabel::Position),
+        "label" => Ok(types::ApartmentLabel::Label),
+        "tenant" => Ok(types::ApartmentLabel::Tenant),
+        _ => Err(format!("Unknown apartment label '{}'.", value)),
+    }
+}
+
+fn parse_page_split(value: &str) -> Result<layout::PageSplit, String> {
+    match value {
+        "continuous" => Ok(layout::PageSplit::Continuous),
+        "half-year" => Ok(layout::PageSplit::HalfYear),
+        "quarter" => Ok(layout::PageSplit::Quarter),
+        _ => Err(format!("Unknown page split '{}'.", value)),
+    }
+}
+
+fn parse_orientation(value: &str) -> Result<layout::Orientation, String> {
+    match value {
+        "portrait" => Ok(layout::Orientation::Portrait),
+        "landscape" => Ok(layout::Orientation::Landscape),
+        _ => Err(format!("Unknown orientation '{}'.", value)),
+    }
+}
+
+fn parse_format(value: &str) -> Result<OutputFormat, String> {
+    match value {
+        "html" => Ok(OutputFormat::Html),
+        "pdf" => Ok(OutputFormat::Pdf),
+        "ics" => Ok(OutputFormat::Ics),
+        "csv" => Ok(OutputFormat::Export(export::ExportFormat::Csv)),
+        "json" => Ok(OutputFormat::Export(export::ExportFormat::Json)),
+        "stats" => Ok(OutputFormat::Stats),
+        _ => Err(format!("Unknown format '{}'.", value)),
+    }
+}
+
+// @param value floor and position separated by a colon, e.g. 2:Left
+// NOTE: the position is resolved once the config is read, see `find_apartment`
+fn parse_apartment(value: &str) -> Result<types::ApartmentPosition, String> {
+    let (floor, position) = value
+        .split_once(':')
+        .ok_or(format!("Invalid apartment '{}'.", value))?;
+    Ok(types::ApartmentPosition {
+        floor: parse_number(floor, "--apartment")?,
+        position: parse_position(position)?,
+    })
+}
+
+// @return the apartment at the position, e.g. the second one for Right on a floor with two
+fn find_apartment(
+    config: &types::Config,
+    apartment: &types::ApartmentPosition,
+) -> Result<plan::ApartmentId, String> {
+    config
+        .position_id(apartment.floor, &apartment.position)
+        .ok_or(format!(
+            "There is no apartment at floor {}, position {:?}.",
+            apartment.floor, apartment.position
+        ))
+}
+
+// @return None if the usage should be printed
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut config = None;
+    let mut year = None;
+    let mut start = None;
+    let mut end = None;
+    let mut floor = None;
+    let mut position = None;
+    let mut days_left = None;
+    let mut holiday_file = None;
+    let mut country = None;
+    let mut subdivision = None;
+    let mut holiday_source = holidays::HolidaySource::Offline;
+    let mut check_holidays = false;
+    let mut cache_dir = None;
+    let mut cache_max_age = None;
+    let mut refresh_holidays = false;
+    let mut exclude_sunday = false;
+    let mut excluded_weekdays = vec![];
+    let mut holiday_policy = None;
+    let mut slots = None;
+    let mut rotation_order = None;
+    let mut fair = false;
+    let mut popular_months = None;
+    let mut locale = None;
+    let mut template = None;
+    let mut apartment_label = None;
+    let mut hide_names = false;
+    let mut months_per_row = None;
+    let mut page_split = None;
+    let mut orientation = None;
+    let mut format = OutputFormat::Html;
+    let mut apartment = None;
+    let mut split_apartments = false;
+    let mut output = None;
+    let mut state_in = None;
+    let mut state_out = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => config = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+            "--year" => year = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?),
+            "--start" => start = Some(parse_date(&next_value(&mut args, &arg)?, &arg)?),
+            "--end" => end = Some(parse_date(&next_value(&mut args, &arg)?, &arg)?),
+            "--floor" => floor = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?),
+            "--position" => {
+                position = Some(parse_position(&next_value(&mut args, &arg)?)?);
+            }
+            "--days-left" => {
+                days_left = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?);
+            }
+            "--holidays" => holiday_file = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+            "--country" => country = Some(next_value(&mut args, &arg)?),
+            "--subdivision" => subdivision = Some(next_value(&mut args, &arg)?),
+            "--online-holidays" => holiday_source = holidays::HolidaySource::Online,
+            "--check-holidays" => check_holidays = true,
+            "--cache-dir" => cache_dir = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+            "--cache-max-age" => {
+                cache_max_age = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?)
+            }
+            "--refresh-holidays" => refresh_holidays = true,
+            "--exclude-sunday" => exclude_sunday = true,
+            "--holiday-policy" => {
+                holiday_policy = Some(parse_holiday_policy(&next_value(&mut args, &arg)?)?)
+            }
+            "--rotation-order" => {
+                rotation_order = Some(parse_rotation_order(&next_value(&mut args, &arg)?)?)
+            }
+            "--fair" => fair = true,
+            "--popular-months" => {
+                popular_months = Some(parse_months(&next_value(&mut args, &arg)?, &arg)?)
+            }
+            "--slots" => {
+                slots = Some(
+                    next_value(&mut args, &arg)?
+                        .split(',')
+                        .map(|label| label.trim().to_owned())
+                        .collect(),
+                );
+            }
+            "--exclude-weekdays" => {
+                excluded_weekdays = parse_weekdays(&next_value(&mut args, &arg)?)?;
+            }
+            "--locale" => locale = Some(parse_locale(&next_value(&mut args, &arg)?)?),
+            "--template" => template = Some(next_value(&mut args, &arg)?),
+            "--apartment-label" => {
+                apartment_label = Some(parse_apartment_label(&next_value(&mut args, &arg)?)?)
+            }
+            "--hide-names" => hide_names = true,
+            "--months-per-row" => {
+                months_per_row = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?)
+            }
+            "--page-split" => page_split = Some(parse_page_split(&next_value(&mut args, &arg)?)?),
+            "--orientation" => {
+                orientation = Some(parse_orientation(&next_value(&mut args, &arg)?)?)
+            }
+            "--format" => format = parse_format(&next_value(&mut args, &arg)?)?,
+            "--apartment" => apartment = Some(parse_apartment(&next_value(&mut args, &arg)?)?),
+            "--split-apartments" => split_apartments = true,
+            "--output" => output = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+            "--state-in" => state_in = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+            "--state-out" => state_out = Some(PathBuf::from(next_value(&mut args, &arg)?)),
+            "--help" | "-h" => return Ok(None),
+            _ => return Err(format!("Unknown argument '{}'.", arg)),
+        }
+    }
+
+    let holidays = match (holiday_file, country) {
+        (Some(_), Some(_)) => {
+            return Err("Use either --holidays or --country, not both.".to_owned());
+        }
+        (Some(file), None) => HolidaySource::File(file),
+        (None, Some(country)) => HolidaySource::Country {
+            country,
+            subdivision: subdivision.unwrap_or_default(),
+            source: holiday_source,
+        },
+        (None, None) => HolidaySource::NoHolidays,
+    };
+
+    let full_year = year.map(types::full_year);
+    let start = start
+        .or(full_year.map(|(start, _)| start))
+        .ok_or("Missing --year or --start.")?;
+    let end = end
+        .or(full_year.map(|(_, end)| end))
+        .ok_or("Missing --year or --end.")?;
+
+    Ok(Some(Args {
+        config: config.ok_or("Missing --config.")?,
+        start,
+        end,
+        apartment_info: match state_in {
+            Some(path) => read_state(&path)?,
+            None => types::ApartmentInfo {
+                current_floor: floor.ok_or("Missing --floor or --state-in.")?,
+                position: position.ok_or("Missing --position or --state-in.")?,
+                days_left: days_left.ok_or("Missing --days-left or --state-in.")?,
+                moved_days: vec![],
+            },
+        },
+        holidays,
+        exclude_sunday,
+        excluded_weekdays,
+        holiday_policy,
+        slots,
+        rotation_order,
+        fair,
+        popular_months,
+        locale,
+        template,
+        apartment_label,
+        hide_names,
+        months_per_row,
+        page_split,
+        orientation,
+        check_holidays,
+        cache_dir,
+        cache_max_age,
+        refresh_holidays,
+        format,
+        apartment,
+        split_apartments,
+        output,
+        state_out,
+    }))
+}
+
+fn read_state(path: &PathBuf) -> Result<types::ApartmentInfo, String> {
+    let content = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&content).map_err(|err| err.to_string())
+}
+
+fn read_holiday_file(path: &PathBuf) -> Result<BTreeMap<LocalDate, String>, String> {
+    let content = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let holidays: BTreeMap<String, String> =
+        serde_json::from_str(&content).map_err(|err| err.to_string())?;
+    holidays
+        .into_iter()
+        .map(|(date, name)| match types::string_to_local_date(&date) {
+            Some(date) => Ok((date, name)),
+            None => Err(format!("Invalid holiday date '{}'.", date)),
+        })
+        .collect()
+}
+
+fn holiday_cache(args: &Args) -> Option<HolidayCache> {
+    let cache = HolidayCache::new(args.cache_dir.clone()?);
+    Some(match args.cache_max_age {
+        Some(days) => cache.with_max_age(Duration::from_secs(days * 24 * 60 * 60)),
+        None => cache,
+    })
+}
+
+async fn load_holidays(args: &Args, locale: Locale) -> Result<BTreeMap<LocalDate, String>, String> {
+    match &args.holidays {
+        HolidaySource::NoHolidays => Ok(BTreeMap::new()),
+        HolidaySource::File(path) => read_holiday_file(path),
+        HolidaySource::Country {
+            country,
+            subdivision,
+            source,
+        } => {
+            let cache = holiday_cache(args);
+            holidays::load_holidays_for_years(
+                args.start.year() as u16,
+                args.end.year() as u16,
+                country.clone(),
+                subdivision.clone(),
+                locale,
+                *source,
+                cache.as_ref(),
+                args.refresh_holidays,
+            )
+            .await
+            .map_err(|err| err.to_string())
+        }
+    }
+}
+
+// prints the dates on which the computed and the downloaded holidays differ to stderr
+async fn report_holiday_differences(args: &Args, locale: Locale) -> Result<(), String> {
+    let HolidaySource::Country {
+        country,
+        subdivision,
+        ..
+    } = &args.holidays
+    else {
+        return Err("--check-holidays requires --country.".to_owned());
+    };
+    let cache = holiday_cache(args);
+    for year in args.start.year()..=args.end.year() {
+        let differences = holidays::cross_check_holidays(
+            year as u16,
+            country.clone(),
+            subdivision.clone(),
+            locale,
+            cache.as_ref(),
+        )
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or(format!("No offline holidays available for {}.", country))?;
+        for difference in differences {
+            eprintln!(
+                "Holiday mismatch on {}: computed {:?}, downloaded {:?}",
+                types::print_full_local_date(difference.date),
+                difference.offline,
+                difference.online
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn run(args: Args) -> Result<(), String> {
+    let mut config = types::config_from_file(&args.config).map_err(|err| err.to_string())?;
+    config.locale = args.locale.unwrap_or(config.locale);
+    if args.template.is_some() {
+        config.template = args.template.clone();
+    }
+    config.apartment_label = args.apartment_label.unwrap_or(config.apartment_label);
+    config.hide_names |= args.hide_names;
+    config.holiday_policy = args.holiday_policy.unwrap_or(config.holiday_policy);
+    if let Some(rotation_order) = &args.rotation_order {
+        config.rotation_order = rotation_order.clone();
+    }
+    // NOTE: popular months only matter to the fair scheduler
+    if args.fair || args.popular_months.is_some() {
+        let mut fairness = match &config.scheduler {
+            fairness::Scheduler::Fair(fairness) => fairness.clone(),
+            fairness::Scheduler::Rotation => fairness::Fairness::default(),
+        };
+        if let Some(popular_months) = &args.popular_months {
+            fairness.popular_months = popular_months.clone();
+        }
+        config.scheduler = fairness::Scheduler::Fair(fairness);
+    }
+    if let Some(slots) = &args.slots {
+        config.slots = slots.clone();
+    }
+    config
+        .excluded_weekdays
+        .extend(args.excluded_weekdays.iter().copied());
+    config.layout.months_per_row = args.months_per_row.unwrap_or(config.layout.months_per_row);
+    config.layout.page_split = args.page_split.unwrap_or(config.layout.page_split);
+    config.layout.orientation = args.orientation.unwrap_or(config.layout.orientation);
+    let apartment = args
+        .apartment
+        .as_ref()
+        .map(|apartment| find_apartment(&config, apartment))
+        .transpose()?;
+    let holidays = load_holidays(&args, config.locale).await?;
+    if args.check_holidays {
+        report_holiday_differences(&args, config.locale).await?;
+    }
+
+    let plan = types::create_plan(
+        &config,
+        args.start,
+        args.end,
+        &args.apartment_info,
+        args.exclude_sunday,
+        &holidays,
+    )
+    .map_err(|err| err.to_string())?;
+
+    if let Some(path) = &args.state_out {
+        let content =
+            serde_json::to_string_pretty(&plan.last_apartment).map_err(|err| err.to_string())?;
+        std::fs::write(path, content).map_err(|err| err.to_string())?;
+    }
+
+    if args.split_apartments {
+        let OutputFormat::Ics = args.format else {
+            return Err("--split-apartments requires --format ics.".to_owned());
+        };
+        let dir = args
+            .output
+            .as_ref()
+            .ok_or("--split-apartments requires --output.")?;
+        std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        return ics::create_apartment_calendars(&config, &plan)
+            .iter()
+            .try_for_each(|calendar| {
+                let file_name = format!(
+                    "waschplan_{}_{}.ics",
+                    calendar.apartment.floor,
+                    calendar.apartment.unit + 1
+                );
+                std::fs::write(dir.join(file_name), &calendar.content)
+                    .map_err(|err| err.to_string())
+            });
+    }
+
+    let content = match args.format {
+        OutputFormat::Html => html::create_year_html(&config, &plan)
+            .map_err(|err| err.to_string())?
+            .into_bytes(),
+        OutputFormat::Pdf => pdf::create_year_pdf(&config, &plan).map_err(|err| err.to_string())?,
+        OutputFormat::Ics => ics::create_calendar(&config, &plan, apartment).into_bytes(),
+        OutputFormat::Export(format) => {
+            let export_days = export::create_export_days(&config, &plan);
+            export::create_export(&export_days, format)
+                .map_err(|err| err.to_string())?
+                .into_bytes()
+        }
+        OutputFormat::Stats => {
+            stats::print_report(&config, &stats::create_stats(&config, &plan)).into_bytes()
+        }
+    };
+
+    match &args.output {
+        Some(path) => std::fs::write(path, content).map_err(|err| err.to_string()),
+        None => std::io::stdout()
+            .write_all(&content)
+            .map_err(|err| err.to_string()),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[test]
+pub fn reject_invalid_args() {
+    let parse = |args: &[&str]| parse_args(args.iter().map(|arg| arg.to_string()));
+    let error = |args: &[&str]| parse(args).err().unwrap_or_default();
+    let apartment = ["--floor", "0", "--position", "Left", "--days-left", "0"];
+
+    assert_eq!(error(&["--unknown"]), "Unknown argument '--unknown'.");
+    assert_eq!(error(&["--config"]), "Missing value for --config.");
+    assert_eq!(
+        error(&["--start", "31.2.2025"]),
+        "Invalid value '31.2.2025' for --start."
+    );
+    assert_eq!(
+        error(&["--year", "next"]),
+        "Invalid value 'next' for --year."
+    );
+    assert_eq!(
+        error(&[&["--config", "config.json"], &apartment[..]].concat()),
+        "Missing --year or --start."
+    );
+    assert_eq!(
+        error(&["--config", "config.json", "--year", "2025"]),
+        "Missing --floor or --state-in."
+    );
+    assert!(matches!(parse(&["--help"]), Ok(None)));
+
+    let args = parse(
+        &[
+            &[
+                "--config",
+                "config.json",
+                "--year",
+                "2025",
+                "--end",
+                "31.3.2026",
+            ],
+            &apartment[..],
+        ]
+        .concat(),
+    )
+    .ok()
+    .flatten()
+    .unwrap();
+    assert_eq!(args.start, types::full_year(2025).0);
+    assert_eq!(types::print_full_local_date(args.end), "31.3.2026");
+}
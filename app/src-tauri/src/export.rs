@@ -0,0 +1,131 @@
+use crate::plan::{self, Plan};
+use crate::types;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+// one row of the exported plan, a day with several slots has a row per slot
+#[derive(Serialize, Debug)]
+pub struct ExportDay {
+    // ISO 8601, e.g. 2025-08-01
+    pub date: String,
+    pub weekday: plan::Weekday,
+    // label of the slot, None for a plan without slots
+    pub slot: Option<String>,
+    // None if nobody washes on this day
+    pub floor: Option<u32>,
+    // apartment on the floor counted from the left starting at 1
+    pub position: Option<usize>,
+    // label as printed in the plan, e.g. "2. Links"
+    pub apartment: Option<String>,
+    pub holiday: Option<String>,
+    // name of a blackout of the house, e.g. a maintenance
+    pub blackout: Option<String>,
+    pub assigned: bool,
+}
+
+const CSV_HEADER: [&str; 9] = [
+    "date",
+    "weekday",
+    "slot",
+    "floor",
+    "position",
+    "apartment",
+    "holiday",
+    "blackout",
+    "assigned",
+];
+
+pub fn create_export_days(config: &types::Config, plan: &Plan) -> Vec<ExportDay> {
+    plan.days
+        .iter()
+        .flat_map(|day| {
+            (0..day.assignments.len()).map(move |slot| {
+                let apartment = day.apartment(slot);
+                ExportDay {
+                    date: plan::print_iso_date(day.date),
+                    weekday: day.weekday,
+                    slot: config.slot_label(slot).map(str::to_owned),
+                    floor: apartment.map(|apartment| apartment.floor),
+                    position: apartment.map(|apartment| apartment.unit + 1),
+                    apartment: apartment.map(|apartment| apartment.print(config)),
+                    holiday: day.holiday.clone(),
+                    blackout: day.blackout.clone(),
+                    assigned: apartment.is_some(),
+                }
+            })
+        })
+        .collect()
+}
+
+// quotes a field if it contains a separator, a quote or a line break
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+pub fn create_csv(export_days: &[ExportDay]) -> String {
+    let mut lines = vec![CSV_HEADER.join(",")];
+    lines.extend(export_days.iter().map(|day| {
+        [
+            day.date.clone(),
+            format!("{:?}", day.weekday),
+            day.slot.clone().unwrap_or_default(),
+            day.floor.map(|floor| floor.to_string()).unwrap_or_default(),
+            day.position
+                .map(|position| position.to_string())
+                .unwrap_or_default(),
+            day.apartment.clone().unwrap_or_default(),
+            day.holiday.clone().unwrap_or_default(),
+            day.blackout.clone().unwrap_or_default(),
+            day.assigned.to_string(),
+        ]
+        .iter()
+        .map(|field| escape_csv(field))
+        .collect::<Vec<String>>()
+        .join(",")
+    }));
+    lines.push("".to_owned());
+    lines.join("\r\n")
+}
+
+pub fn create_json(export_days: &[ExportDay]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(export_days)
+}
+
+pub fn create_export(
+    export_days: &[ExportDay],
+    format: ExportFormat,
+) -> serde_json::Result<String> {
+    match format {
+        ExportFormat::Csv => Ok(create_csv(export_days)),
+        ExportFormat::Json => create_json(export_days),
+    }
+}
+
+#[test]
+pub fn quote_csv_fields() {
+    let export_days = [ExportDay {
+        date: "2025-08-01".to_owned(),
+        weekday: plan::Weekday::Friday,
+        slot: None,
+        floor: None,
+        position: None,
+        apartment: None,
+        holiday: Some("Bundesfeiertag, \"1. August\"".to_owned()),
+        blackout: None,
+        assigned: false,
+    }];
+    assert_eq!(
+        create_csv(&export_days),
+        "date,weekday,slot,floor,position,apartment,holiday,blackout,assigned\r\n\
+         2025-08-01,Friday,,,,,\"Bundesfeiertag, \"\"1. August\"\"\",,false\r\n"
+    );
+}
@@ -0,0 +1,319 @@
+use crate::plan::{ApartmentId, Assignment, Plan, PlanDay, SkipReason, Weekday};
+use crate::types::{self, Apartment};
+use serde::{Deserialize, Serialize};
+
+// how the apartments take turns
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub enum Scheduler {
+    // the apartments wash in the order of the floors and positions
+    #[default]
+    Rotation,
+    // every turn goes to the apartment which is furthest behind its share
+    Fair(Fairness),
+}
+
+// The weights set how much an even spread of Saturdays and of days in popular months counts
+// compared to an even spread of all days. The share of an apartment is the number of days of its
+// turn, an apartment with two days per turn should get twice as many days as one with a single day.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Fairness {
+    // months in which laundry days are in demand, 1 to 12, e.g. the summer months
+    pub popular_months: Vec<u8>,
+    pub saturday_weight: f32,
+    pub popular_month_weight: f32,
+}
+
+impl Default for Fairness {
+    fn default() -> Self {
+        Fairness {
+            popular_months: vec![],
+            saturday_weight: 1.0,
+            popular_month_weight: 1.0,
+        }
+    }
+}
+
+impl Scheduler {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::Rotation => true,
+            Self::Fair(fairness) => fairness.is_valid(),
+        }
+    }
+
+    pub fn popular_months(&self) -> &[u8] {
+        match self {
+            Self::Rotation => &[],
+            Self::Fair(fairness) => &fairness.popular_months,
+        }
+    }
+}
+
+impl Fairness {
+    fn is_valid(&self) -> bool {
+        self.popular_months
+            .iter()
+            .all(|month| (1..=12).contains(month))
+            && self.saturday_weight >= 0.0
+            && self.popular_month_weight >= 0.0
+    }
+
+    // @return the days, Saturdays and days in popular months of a day
+    fn count(&self, day: &PlanDay) -> Tally {
+        Tally([
+            1.0,
+            (day.weekday == Weekday::Saturday) as u8 as f32,
+            self.popular_months.contains(&day.month()) as u8 as f32,
+        ])
+    }
+
+    fn weights(&self) -> Tally {
+        Tally([1.0, self.saturday_weight, self.popular_month_weight])
+    }
+}
+
+// days, Saturdays and days in popular months
+#[derive(Clone, Copy, Debug, Default)]
+struct Tally([f32; 3]);
+
+impl Tally {
+    fn add(&mut self, other: &Tally) {
+        self.0
+            .iter_mut()
+            .zip(other.0)
+            .for_each(|(count, other)| *count += other);
+    }
+}
+
+// The plan is balanced if the sum of count² / share over all apartments is small for every kind
+// of day, it is smallest if every apartment gets days in proportion to its share.
+// @return how much the turn adds to this sum per day of the turn
+fn cost(weights: &Tally, tally: &Tally, turn: &Tally, share: f32) -> f32 {
+    let added: f32 = (0..3)
+        .map(|kind| weights.0[kind] * turn.0[kind] * (2.0 * tally.0[kind] + turn.0[kind]) / share)
+        .sum();
+    added / turn.0[0]
+}
+
+// a slot in which somebody may wash, a vacancy of the rotation is only open to the others if
+// the vacancy policy passes it on
+fn is_open(config: &types::Config, assignment: &Assignment) -> bool {
+    match assignment {
+        Assignment::Apartment(_) => true,
+        Assignment::Skipped(SkipReason::Vacancy(_)) => {
+            config.vacancy_policy == types::VacancyPolicy::PassOn
+        }
+        Assignment::Skipped(_) => false,
+    }
+}
+
+// Hands out the open slots of a plan created by the rotation turn by turn. Every turn goes to the
+// present apartment which adds the least to the imbalance, ties keep the rotation order.
+// NOTE: the holiday policy only decides which days are open, every turn gets all of its days anyway
+// @param last_apartment the apartment the plan was started with
+// @param moved_days days moved by a holiday at the end of the previous plan, made up first
+pub fn balance(
+    config: &types::Config,
+    fairness: &Fairness,
+    plan: &mut Plan,
+    last_apartment: &Apartment,
+    moved_days: &[ApartmentId],
+) {
+    let order = types::rotation_order(config, last_apartment);
+    let shares: Vec<f32> = order
+        .iter()
+        .map(|apartment| config.days_total(*apartment).max(1) as f32)
+        .collect();
+    let weights = fairness.weights();
+    let mut tallies = vec![Tally::default(); order.len()];
+    let slots: Vec<(usize, usize)> = plan
+        .days
+        .iter()
+        .enumerate()
+        .flat_map(|(index, day)| {
+            day.assignments
+                .iter()
+                .enumerate()
+                .filter(|(_, assignment)| is_open(config, assignment))
+                .map(move |(slot, _)| (index, slot))
+        })
+        .collect();
+    let counts: Vec<Tally> = slots
+        .iter()
+        .map(|(index, _)| fairness.count(&plan.days[*index]))
+        .collect();
+
+    // the apartment the plan starts with finishes its turn first if it takes part in the rotation
+    let mut current = order.len() - 1;
+    let mut days_left = if order[current] == last_apartment.id() {
+        last_apartment.info(&config.position_map).days_left as usize
+    } else {
+        0
+    };
+    let mut slot = 0;
+    let mut made_up = 0;
+    for apartment in moved_days {
+        let Some((index, day_slot)) = slots.get(slot).copied() else {
+            break;
+        };
+        made_up += 1;
+        // NOTE: an absent apartment loses its moved day like in the rotation
+        if !config.is_absent(*apartment, plan.days[index].date) {
+            plan.days[index].assignments[day_slot] = Assignment::Apartment(*apartment);
+            if let Some(position) = order.iter().position(|id| id == apartment) {
+                tallies[position].add(&counts[slot]);
+            }
+            slot += 1;
+        }
+    }
+    while slot < slots.len() {
+        let (index, day_slot) = slots[slot];
+        let date = plan.days[index].date;
+        if days_left > 0 && !config.is_absent(order[current], date) {
+            plan.days[index].assignments[day_slot] = Assignment::Apartment(order[current]);
+            tallies[current].add(&counts[slot]);
+            days_left -= 1;
+            slot += 1;
+            continue;
+        }
+        let next = (1..=order.len())
+            .map(|offset| (current + offset) % order.len())
+            .filter(|candidate| !config.is_absent(order[*candidate], date))
+            .min_by(|a, b| {
+                let cost = |candidate: usize| {
+                    let mut turn = Tally::default();
+                    counts[slot..]
+                        .iter()
+                        .take(shares[candidate] as usize)
+                        .for_each(|count| turn.add(count));
+                    cost(&weights, &tallies[candidate], &turn, shares[candidate])
+                };
+                cost(*a).total_cmp(&cost(*b))
+            });
+        match next {
+            Some(next) => {
+                current = next;
+                days_left = shares[next] as usize;
+            }
+            // NOTE: nobody is present, the slot stays free
+            None => {
+                let vacancy = order[(current + 1) % order.len()];
+                plan.days[index].assignments[day_slot] =
+                    Assignment::Skipped(SkipReason::Vacancy(vacancy));
+                days_left = 0;
+                slot += 1;
+            }
+        }
+    }
+    // NOTE: moved days without an open slot are made up in the next plan
+    plan.last_apartment = types::ApartmentInfo {
+        moved_days: moved_days[made_up..].to_vec(),
+        ..config.apartment_info(order[current], days_left as u8)
+    };
+}
+
+#[test]
+pub fn balance_saturdays() {
+    let mut config = types::sample_config();
+    let create_stats = |config: &types::Config| {
+        let plan = types::sample_plan(config, 2025);
+        crate::stats::create_stats(config, &plan)
+    };
+    // Saturdays per day of a turn
+    let spread = |stats: &[crate::stats::ApartmentStats]| {
+        let rates: Vec<f32> = stats
+            .iter()
+            .map(|stats| stats.saturdays as f32 / stats.days_total as f32)
+            .collect();
+        rates.iter().cloned().fold(f32::MIN, f32::max)
+            - rates.iter().cloned().fold(f32::MAX, f32::min)
+    };
+
+    let rotation = create_stats(&config);
+    config.scheduler = Scheduler::Fair(Fairness {
+        popular_months: vec![7, 8],
+        ..Fairness::default()
+    });
+    let fair = create_stats(&config);
+    assert!(spread(&fair) < spread(&rotation));
+    assert_eq!(
+        fair.iter().map(|stats| stats.days).sum::<usize>(),
+        rotation.iter().map(|stats| stats.days).sum::<usize>()
+    );
+}
+
+#[test]
+pub fn keep_custom_order() {
+    let mut config = types::sample_config();
+    config.rotation_order = types::RotationOrder::Custom(
+        serde_json::from_str(
+            r#"[{"floor": 1, "position": "Left"}, {"floor": 1, "position": "Middle"}]"#,
+        )
+        .unwrap(),
+    );
+    config.scheduler = Scheduler::Fair(Fairness::default());
+    let apartment_info = types::ApartmentInfo {
+        current_floor: 0,
+        position: types::FloorPosition::Left,
+        days_left: 1,
+        moved_days: vec![],
+    };
+    let plan = types::create_full_year(
+        &config,
+        2025,
+        &apartment_info,
+        false,
+        &std::collections::BTreeMap::new(),
+    )
+    .unwrap();
+
+    // the apartment to start from isn't listed and doesn't wash
+    assert!(plan.days.iter().all(|day| day
+        .apartments()
+        .iter()
+        .all(|apartment| apartment.floor == 1 && apartment.unit < 2)));
+}
+
+#[test]
+pub fn keep_free_and_moved_days() {
+    let mut config = types::sample_config();
+    config.vacancy_policy = types::VacancyPolicy::LeaveFree;
+    config.apartments =
+        serde_json::from_str(r#"[{"floor": 1, "position": "Left", "vacant": true}]"#).unwrap();
+    let free_days = |plan: &Plan| -> Vec<datetime::LocalDate> {
+        plan.days
+            .iter()
+            .filter(|day| {
+                matches!(
+                    day.assignments[0],
+                    Assignment::Skipped(SkipReason::Vacancy(_))
+                )
+            })
+            .map(|day| day.date)
+            .collect()
+    };
+
+    let rotation = types::sample_plan(&config, 2025);
+    config.scheduler = Scheduler::Fair(Fairness::default());
+    let fair = types::sample_plan(&config, 2025);
+    assert!(!free_days(&rotation).is_empty());
+    assert_eq!(free_days(&fair), free_days(&rotation));
+
+    // the day moved at the end of the previous plan is made up first
+    let middle = ApartmentId { floor: 2, unit: 1 };
+    let apartment_info = types::ApartmentInfo {
+        moved_days: vec![middle],
+        ..types::sample_state()
+    };
+    let plan = types::create_full_year(
+        &config,
+        2025,
+        &apartment_info,
+        true,
+        &std::collections::BTreeMap::new(),
+    )
+    .unwrap();
+    assert_eq!(plan.days[0].apartment(0), Some(middle));
+    assert!(plan.last_apartment.moved_days.is_empty());
+}
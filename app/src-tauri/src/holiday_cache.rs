@@ -0,0 +1,176 @@
+use crate::i18n::Locale;
+use crate::types;
+use datetime::LocalDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+// On-disk copy of the holidays fetched from openholidaysapi.org,
+// one file per (year, country, subdivision, locale).
+pub struct HolidayCache {
+    dir: PathBuf,
+    max_age: Duration,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CacheFile {
+    // seconds since the unix epoch
+    fetched_at: u64,
+    // @key date as written by `types::print_full_local_date`
+    holidays: BTreeMap<String, String>,
+}
+
+pub struct CachedHolidays {
+    pub holidays: BTreeMap<LocalDate, String>,
+    pub is_expired: bool,
+}
+
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+impl HolidayCache {
+    pub fn new(dir: PathBuf) -> HolidayCache {
+        HolidayCache {
+            dir,
+            max_age: DEFAULT_MAX_AGE,
+        }
+    }
+
+    pub fn with_max_age(self, max_age: Duration) -> HolidayCache {
+        HolidayCache { max_age, ..self }
+    }
+
+    // @return None unless the codes are ISO 3166 codes and the subdivision is part of the country
+    // NOTE: the codes come from the frontend and must not leave the cache directory, e.g. "../x"
+    fn path(
+        &self,
+        year: u16,
+        country_code: &str,
+        division_code: &str,
+        locale: Locale,
+    ) -> Option<PathBuf> {
+        rust_iso3166::from_alpha2(country_code)?;
+        if !division_code.is_empty()
+            && (rust_iso3166::iso3166_2::from_code(division_code).is_none()
+                || !division_code.starts_with(&format!("{}-", country_code)))
+        {
+            return None;
+        }
+        let division = if division_code.is_empty() {
+            "all"
+        } else {
+            division_code
+        };
+        Some(self.dir.join(format!(
+            "holidays_{}_{}_{}_{}.json",
+            year,
+            country_code,
+            division,
+            locale.iso_code()
+        )))
+    }
+
+    // @return None if nothing is cached, the file can't be read or the codes are invalid
+    pub fn read(
+        &self,
+        year: u16,
+        country_code: &str,
+        division_code: &str,
+        locale: Locale,
+    ) -> Option<CachedHolidays> {
+        let content =
+            std::fs::read_to_string(self.path(year, country_code, division_code, locale)?).ok()?;
+        let file: CacheFile = serde_json::from_str(&content).ok()?;
+        let holidays = file
+            .holidays
+            .iter()
+            .map(|(date, name)| types::string_to_local_date(date).map(|date| (date, name.clone())))
+            .collect::<Option<BTreeMap<LocalDate, String>>>()?;
+        let is_expired = now().saturating_sub(file.fetched_at) > self.max_age.as_secs();
+        Some(CachedHolidays {
+            holidays,
+            is_expired,
+        })
+    }
+
+    pub fn write(
+        &self,
+        year: u16,
+        country_code: &str,
+        division_code: &str,
+        locale: Locale,
+        holidays: &BTreeMap<LocalDate, String>,
+    ) -> std::io::Result<()> {
+        let path =
+            self.path(year, country_code, division_code, locale)
+                .ok_or(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Invalid region '{}' '{}'.", country_code, division_code),
+                ))?;
+        let file = CacheFile {
+            fetched_at: now(),
+            holidays: holidays
+                .iter()
+                .map(|(date, name)| (types::print_full_local_date(*date), name.clone()))
+                .collect(),
+        };
+        std::fs::create_dir_all(&self.dir)?;
+        let content = serde_json::to_string_pretty(&file)?;
+        std::fs::write(path, content)
+    }
+}
+
+#[test]
+pub fn expire_cached_holidays() {
+    let dir = std::env::temp_dir().join(format!("waschplan_cache_{}", std::process::id()));
+    let cache = HolidayCache::new(dir.clone());
+    let new_year = LocalDate::ymd(2025, datetime::Month::January, 1).unwrap();
+    let holidays = BTreeMap::from([(new_year, "Neujahr".to_string())]);
+
+    assert!(cache.read(2025, "CH", "", Locale::De).is_none());
+    cache.write(2025, "CH", "", Locale::De, &holidays).unwrap();
+    let cached = cache.read(2025, "CH", "", Locale::De).unwrap();
+    assert_eq!(cached.holidays, holidays);
+    assert!(!cached.is_expired);
+    // other subdivisions and locales are cached separately
+    assert!(cache.read(2025, "CH", "CH-ZH", Locale::De).is_none());
+    assert!(cache.read(2025, "CH", "", Locale::Fr).is_none());
+    // only ISO codes become part of a file name
+    assert!(cache
+        .write(2025, "../CH", "", Locale::De, &holidays)
+        .is_err());
+    assert!(cache
+        .write(2025, "CH", "../../x", Locale::De, &holidays)
+        .is_err());
+    assert!(cache
+        .write(2025, "CH", "DE-BY", Locale::De, &holidays)
+        .is_err());
+    assert!(cache
+        .write(2025, "CH", "CH-ZH", Locale::De, &holidays)
+        .is_ok());
+
+    // a copy fetched a day ago
+    let file = CacheFile {
+        fetched_at: now() - 24 * 60 * 60,
+        holidays: BTreeMap::from([("1.1.2025".to_string(), "Neujahr".to_string())]),
+    };
+    std::fs::write(
+        cache.path(2025, "CH", "", Locale::De).unwrap(),
+        serde_json::to_string(&file).unwrap(),
+    )
+    .unwrap();
+    let cache = cache.with_max_age(Duration::ZERO);
+    let cached = cache.read(2025, "CH", "", Locale::De).unwrap();
+    assert_eq!(cached.holidays, holidays);
+    assert!(cached.is_expired);
+    let cache = cache.with_max_age(DEFAULT_MAX_AGE);
+    assert!(!cache.read(2025, "CH", "", Locale::De).unwrap().is_expired);
+    std::fs::remove_dir_all(dir).unwrap();
+}
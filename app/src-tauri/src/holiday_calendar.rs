@@ -0,0 +1,429 @@
+use crate::i18n::Locale;
+use datetime::{DatePiece, LocalDate, Month, Weekday};
+use std::collections::BTreeMap;
+
+// Offline calculation of the public holidays of Switzerland, Germany and Austria.
+// The regions use the ISO 3166-2 codes returned by `get_subdivisions`.
+
+enum HolidayDate {
+    // month starting at 1, day of month
+    Fixed(i8, i8),
+    // days after easter sunday
+    Easter(i64),
+    Computed(fn(i64) -> LocalDate),
+}
+
+enum Regions {
+    All,
+    Only(&'static [&'static str]),
+    Except(&'static [&'static str]),
+}
+
+struct HolidayRule {
+    name: &'static str,
+    date: HolidayDate,
+    regions: Regions,
+    // first year in which the holiday exists
+    since: Option<i64>,
+}
+
+const fn rule(name: &'static str, date: HolidayDate, regions: Regions) -> HolidayRule {
+    HolidayRule {
+        name,
+        date,
+        regions,
+        since: None,
+    }
+}
+
+const fn rule_since(
+    name: &'static str,
+    date: HolidayDate,
+    regions: Regions,
+    since: i64,
+) -> HolidayRule {
+    HolidayRule {
+        name,
+        date,
+        regions,
+        since: Some(since),
+    }
+}
+
+const SWITZERLAND: &[HolidayRule] = &[
+    rule("Neujahrstag", HolidayDate::Fixed(1, 1), Regions::All),
+    rule(
+        "Berchtoldstag",
+        HolidayDate::Fixed(1, 2),
+        Regions::Only(&[
+            "CH-BE", "CH-GL", "CH-JU", "CH-LU", "CH-NW", "CH-OW", "CH-SG", "CH-SH", "CH-SO",
+            "CH-TG", "CH-VD", "CH-VS", "CH-ZG",
+        ]),
+    ),
+    rule(
+        "Ausrufung der Republik",
+        HolidayDate::Fixed(3, 1),
+        Regions::Only(&["CH-NE"]),
+    ),
+    rule(
+        "Josephstag",
+        HolidayDate::Fixed(3, 19),
+        Regions::Only(&["CH-NW", "CH-SZ", "CH-TI", "CH-UR", "CH-VS"]),
+    ),
+    rule(
+        "Näfelser Fahrt",
+        HolidayDate::Computed(naefelser_fahrt),
+        Regions::Only(&["CH-GL"]),
+    ),
+    rule(
+        "Karfreitag",
+        HolidayDate::Easter(-2),
+        Regions::Except(&["CH-TI", "CH-VS"]),
+    ),
+    rule("Ostermontag", HolidayDate::Easter(1), Regions::All),
+    rule(
+        "Tag der Arbeit",
+        HolidayDate::Fixed(5, 1),
+        Regions::Only(&[
+            "CH-BL", "CH-BS", "CH-JU", "CH-NE", "CH-SH", "CH-TG", "CH-TI", "CH-ZH",
+        ]),
+    ),
+    rule("Auffahrt", HolidayDate::Easter(39), Regions::All),
+    rule("Pfingstmontag", HolidayDate::Easter(50), Regions::All),
+    rule(
+        "Fronleichnam",
+        HolidayDate::Easter(60),
+        Regions::Only(&[
+            "CH-AI", "CH-FR", "CH-JU", "CH-LU", "CH-NE", "CH-NW", "CH-OW", "CH-SZ", "CH-TI",
+            "CH-UR", "CH-VS", "CH-ZG",
+        ]),
+    ),
+    rule(
+        "Jahrestag der Jura-Abstimmung",
+        HolidayDate::Fixed(6, 23),
+        Regions::Only(&["CH-JU"]),
+    ),
+    rule(
+        "Peter und Paul",
+        HolidayDate::Fixed(6, 29),
+        Regions::Only(&["CH-TI"]),
+    ),
+    rule("Bundesfeiertag", HolidayDate::Fixed(8, 1), Regions::All),
+    rule(
+        "Mariä Himmelfahrt",
+        HolidayDate::Fixed(8, 15),
+        Regions::Only(&[
+            "CH-AI", "CH-FR", "CH-JU", "CH-LU", "CH-NW", "CH-OW", "CH-SZ", "CH-TI", "CH-UR",
+            "CH-VS", "CH-ZG",
+        ]),
+    ),
+    rule(
+        "Genfer Bettag",
+        HolidayDate::Computed(jeune_genevois),
+        Regions::Only(&["CH-GE"]),
+    ),
+    rule(
+        "Eidgenössischer Dank-, Buss- und Bettag",
+        HolidayDate::Computed(bettag),
+        Regions::Only(&[
+            "CH-BE", "CH-BL", "CH-BS", "CH-GL", "CH-GR", "CH-LU", "CH-NE", "CH-NW", "CH-OW",
+            "CH-SG", "CH-SH", "CH-SZ", "CH-TG", "CH-UR", "CH-VD", "CH-VS", "CH-ZG", "CH-ZH",
+        ]),
+    ),
+    rule(
+        "Bettagsmontag",
+        HolidayDate::Computed(bettagsmontag),
+        Regions::Only(&["CH-VD"]),
+    ),
+    rule(
+        "Bruder Klaus",
+        HolidayDate::Fixed(9, 25),
+        Regions::Only(&["CH-OW"]),
+    ),
+    rule(
+        "Allerheiligen",
+        HolidayDate::Fixed(11, 1),
+        Regions::Only(&[
+            "CH-AI", "CH-FR", "CH-GL", "CH-GR", "CH-JU", "CH-LU", "CH-NW", "CH-OW", "CH-SG",
+            "CH-SZ", "CH-TI", "CH-UR", "CH-VS", "CH-ZG",
+        ]),
+    ),
+    rule(
+        "Mariä Empfängnis",
+        HolidayDate::Fixed(12, 8),
+        Regions::Only(&[
+            "CH-AI", "CH-FR", "CH-LU", "CH-NW", "CH-OW", "CH-SO", "CH-SZ", "CH-TI", "CH-UR",
+            "CH-VS", "CH-ZG",
+        ]),
+    ),
+    rule("Weihnachten", HolidayDate::Fixed(12, 25), Regions::All),
+    rule(
+        "Stephanstag",
+        HolidayDate::Fixed(12, 26),
+        Regions::Except(&["CH-AI", "CH-GE", "CH-JU", "CH-NE", "CH-VD"]),
+    ),
+    rule(
+        "Restauration der Republik",
+        HolidayDate::Fixed(12, 31),
+        Regions::Only(&["CH-GE"]),
+    ),
+];
+
+const GERMANY: &[HolidayRule] = &[
+    rule("Neujahr", HolidayDate::Fixed(1, 1), Regions::All),
+    rule(
+        "Heilige Drei Könige",
+        HolidayDate::Fixed(1, 6),
+        Regions::Only(&["DE-BW", "DE-BY", "DE-ST"]),
+    ),
+    rule_since(
+        "Internationaler Frauentag",
+        HolidayDate::Fixed(3, 8),
+        Regions::Only(&["DE-BE"]),
+        2019,
+    ),
+    rule_since(
+        "Internationaler Frauentag",
+        HolidayDate::Fixed(3, 8),
+        Regions::Only(&["DE-MV"]),
+        2023,
+    ),
+    rule("Karfreitag", HolidayDate::Easter(-2), Regions::All),
+    rule(
+        "Ostersonntag",
+        HolidayDate::Easter(0),
+        Regions::Only(&["DE-BB"]),
+    ),
+    rule("Ostermontag", HolidayDate::Easter(1), Regions::All),
+    rule("Tag der Arbeit", HolidayDate::Fixed(5, 1), Regions::All),
+    rule("Christi Himmelfahrt", HolidayDate::Easter(39), Regions::All),
+    rule(
+        "Pfingstsonntag",
+        HolidayDate::Easter(49),
+        Regions::Only(&["DE-BB"]),
+    ),
+    rule("Pfingstmontag", HolidayDate::Easter(50), Regions::All),
+    rule(
+        "Fronleichnam",
+        HolidayDate::Easter(60),
+        Regions::Only(&["DE-BW", "DE-BY", "DE-HE", "DE-NW", "DE-RP", "DE-SL"]),
+    ),
+    rule(
+        "Mariä Himmelfahrt",
+        HolidayDate::Fixed(8, 15),
+        Regions::Only(&["DE-SL"]),
+    ),
+    rule_since(
+        "Weltkindertag",
+        HolidayDate::Fixed(9, 20),
+        Regions::Only(&["DE-TH"]),
+        2019,
+    ),
+    rule(
+        "Tag der Deutschen Einheit",
+        HolidayDate::Fixed(10, 3),
+        Regions::All,
+    ),
+    rule(
+        "Reformationstag",
+        HolidayDate::Fixed(10, 31),
+        Regions::Only(&["DE-BB", "DE-MV", "DE-SN", "DE-ST", "DE-TH"]),
+    ),
+    rule_since(
+        "Reformationstag",
+        HolidayDate::Fixed(10, 31),
+        Regions::Only(&["DE-HB", "DE-HH", "DE-NI", "DE-SH"]),
+        2018,
+    ),
+    rule(
+        "Allerheiligen",
+        HolidayDate::Fixed(11, 1),
+        Regions::Only(&["DE-BW", "DE-BY", "DE-NW", "DE-RP", "DE-SL"]),
+    ),
+    rule(
+        "Buß- und Bettag",
+        HolidayDate::Computed(buss_und_bettag),
+        Regions::Only(&["DE-SN"]),
+    ),
+    rule(
+        "1. Weihnachtsfeiertag",
+        HolidayDate::Fixed(12, 25),
+        Regions::All,
+    ),
+    rule(
+        "2. Weihnachtsfeiertag",
+        HolidayDate::Fixed(12, 26),
+        Regions::All,
+    ),
+];
+
+const AUSTRIA: &[HolidayRule] = &[
+    rule("Neujahr", HolidayDate::Fixed(1, 1), Regions::All),
+    rule(
+        "Heilige Drei Könige",
+        HolidayDate::Fixed(1, 6),
+        Regions::All,
+    ),
+    rule("Ostermontag", HolidayDate::Easter(1), Regions::All),
+    rule("Staatsfeiertag", HolidayDate::Fixed(5, 1), Regions::All),
+    rule("Christi Himmelfahrt", HolidayDate::Easter(39), Regions::All),
+    rule("Pfingstmontag", HolidayDate::Easter(50), Regions::All),
+    rule("Fronleichnam", HolidayDate::Easter(60), Regions::All),
+    rule("Mariä Himmelfahrt", HolidayDate::Fixed(8, 15), Regions::All),
+    rule("Nationalfeiertag", HolidayDate::Fixed(10, 26), Regions::All),
+    rule("Allerheiligen", HolidayDate::Fixed(11, 1), Regions::All),
+    rule("Mariä Empfängnis", HolidayDate::Fixed(12, 8), Regions::All),
+    rule("Christtag", HolidayDate::Fixed(12, 25), Regions::All),
+    rule("Stefanitag", HolidayDate::Fixed(12, 26), Regions::All),
+];
+
+fn rules_of_country(country_code: &str) -> Option<&'static [HolidayRule]> {
+    match country_code {
+        "CH" => Some(SWITZERLAND),
+        "DE" => Some(GERMANY),
+        "AT" => Some(AUSTRIA),
+        _ => None,
+    }
+}
+
+pub fn is_supported(country_code: &str) -> bool {
+    rules_of_country(country_code).is_some()
+}
+
+// Gregorian computus (Meeus/Jones/Butcher)
+pub fn easter_sunday(year: i64) -> LocalDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    // NOTE: safe b/c the computus always yields a day in march or april
+    LocalDate::ymd(year, Month::from_one(month as i8).unwrap(), day as i8).unwrap()
+}
+
+fn add_days(date: LocalDate, days: i64) -> LocalDate {
+    // NOTE: `yd` wraps into the neighbouring year if the day is out of range
+    LocalDate::yd(date.year(), date.yearday() as i64 + days).unwrap()
+}
+
+fn days_from_monday(weekday: Weekday) -> i64 {
+    match weekday {
+        Weekday::Monday => 0,
+        Weekday::Tuesday => 1,
+        Weekday::Wednesday => 2,
+        Weekday::Thursday => 3,
+        Weekday::Friday => 4,
+        Weekday::Saturday => 5,
+        Weekday::Sunday => 6,
+    }
+}
+
+// @param nth starting at 1
+fn nth_weekday_of_month(year: i64, month: Month, weekday: Weekday, nth: i64) -> LocalDate {
+    let first = LocalDate::ymd(year, month, 1).unwrap();
+    let offset = (days_from_monday(weekday) - days_from_monday(first.weekday())).rem_euclid(7);
+    add_days(first, offset + 7 * (nth - 1))
+}
+
+// third sunday of september
+fn bettag(year: i64) -> LocalDate {
+    nth_weekday_of_month(year, Month::September, Weekday::Sunday, 3)
+}
+
+fn bettagsmontag(year: i64) -> LocalDate {
+    add_days(bettag(year), 1)
+}
+
+// thursday after the first sunday of september
+fn jeune_genevois(year: i64) -> LocalDate {
+    add_days(
+        nth_weekday_of_month(year, Month::September, Weekday::Sunday, 1),
+        4,
+    )
+}
+
+// first thursday of april, moved by a week if it falls on maundy thursday
+fn naefelser_fahrt(year: i64) -> LocalDate {
+    let date = nth_weekday_of_month(year, Month::April, Weekday::Thursday, 1);
+    if date == add_days(easter_sunday(year), -3) {
+        add_days(date, 7)
+    } else {
+        date
+    }
+}
+
+// last wednesday before the 23rd of november
+fn buss_und_bettag(year: i64) -> LocalDate {
+    let date = LocalDate::ymd(year, Month::November, 22).unwrap();
+    let offset =
+        (days_from_monday(date.weekday()) - days_from_monday(Weekday::Wednesday)).rem_euclid(7);
+    add_days(date, -offset)
+}
+
+impl HolidayRule {
+    fn applies_to(&self, year: i64, division_code: &str) -> bool {
+        if self.since.is_some_and(|since| year < since) {
+            return false;
+        }
+        match self.regions {
+            Regions::All => true,
+            Regions::Only(regions) => regions.contains(&division_code),
+            Regions::Except(regions) => {
+                !division_code.is_empty() && !regions.contains(&division_code)
+            }
+        }
+    }
+
+    fn date(&self, year: i64) -> LocalDate {
+        match self.date {
+            // NOTE: the rules only contain valid dates
+            HolidayDate::Fixed(month, day) => {
+                LocalDate::ymd(year, Month::from_one(month).unwrap(), day).unwrap()
+            }
+            HolidayDate::Easter(offset) => add_days(easter_sunday(year), offset),
+            HolidayDate::Computed(compute) => compute(year),
+        }
+    }
+}
+
+// @param division_code ISO 3166-2 code of the region, if empty only the nationwide holidays are returned
+// @return None if the country isn't supported
+pub fn compute_holidays(
+    year: u16,
+    country_code: &str,
+    division_code: &str,
+    locale: Locale,
+) -> Option<BTreeMap<LocalDate, String>> {
+    let year = year as i64;
+    let holidays = rules_of_country(country_code)?
+        .iter()
+        .filter(|rule| rule.applies_to(year, division_code))
+        .map(|rule| (rule.date(year), locale.holiday(rule.name)))
+        .collect();
+    Some(holidays)
+}
+
+#[test]
+pub fn compute_movable_feasts() {
+    let date = |month, day| LocalDate::ymd(2025, Month::from_one(month).unwrap(), day).unwrap();
+    let holidays = compute_holidays(2025, "CH", "CH-ZH", Locale::De).unwrap();
+    assert_eq!(easter_sunday(2025), date(4, 20));
+    assert_eq!(holidays.get(&date(4, 18)).unwrap(), "Karfreitag");
+    assert_eq!(holidays.get(&date(5, 29)).unwrap(), "Auffahrt");
+    assert_eq!(holidays.get(&date(6, 9)).unwrap(), "Pfingstmontag");
+    assert_eq!(
+        holidays.get(&date(9, 21)).unwrap(),
+        "Eidgenössischer Dank-, Buss- und Bettag"
+    );
+    assert!(!holidays.contains_key(&date(6, 19)));
+    assert_eq!(buss_und_bettag(2025), date(11, 19));
+}
@@ -1,96 +1,321 @@
-use std::{collections::{BTreeMap, HashMap}, str::FromStr};
-use serde::Deserialize;
+use crate::holiday_cache::HolidayCache;
+use crate::holiday_calendar;
+use crate::i18n::Locale;
 use datetime::LocalDate;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt,
+    str::FromStr,
+};
+
+// Offline: computed by `holiday_calendar`, the API is only asked for unsupported countries
+// Online: always taken from openholidaysapi.org
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HolidaySource {
+    #[default]
+    Offline,
+    Online,
+}
+
+#[derive(Debug)]
+pub enum HolidayError {
+    Network(reqwest::Error),
+    Status(reqwest::StatusCode),
+    Schema(serde_json::Error),
+    // @param start date of the holiday without a name
+    MissingName(String),
+    InvalidDate(String),
+    UnknownCountry(String),
+    // @param iso code of the country without a name
+    MissingCountryName(String),
+}
+
+impl Error for HolidayError {}
+
+impl fmt::Display for HolidayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Network(err) => write!(f, "Could not reach the holiday server: {}", err),
+            Self::Status(status) => write!(f, "The holiday server answered with {}.", status),
+            Self::Schema(err) => write!(f, "Unexpected answer of the holiday server: {}", err),
+            Self::MissingName(date) => write!(f, "The holiday on {} has no name.", date),
+            Self::InvalidDate(date) => write!(f, "Invalid holiday date '{}'.", date),
+            Self::UnknownCountry(iso_code) => write!(f, "Unknown country code '{}'.", iso_code),
+            Self::MissingCountryName(iso_code) => {
+                write!(f, "The country {} has no name.", iso_code)
+            }
+        }
+    }
+}
+
+// A date which only one of the two sources considers a holiday
+#[derive(Debug)]
+pub struct HolidayDifference {
+    pub date: LocalDate,
+    pub offline: Option<String>,
+    pub online: Option<String>,
+}
 
 #[derive(Deserialize, Debug)]
 struct HolidayName {
-    text: String
+    language: Option<String>,
+    text: String,
 }
 
 #[derive(Deserialize, Debug)]
 #[allow(non_snake_case)]
 struct HolidayConstructor {
     startDate: String,
-    name: Vec<HolidayName>
+    name: Vec<HolidayName>,
 }
 
 #[derive(Debug)]
 pub struct Holiday {
     date: LocalDate,
-    name: String
+    name: String,
 }
 
 #[derive(Deserialize, Debug)]
 struct CountryName {
-    text: String
+    text: String,
 }
 
 #[derive(Deserialize, Debug)]
 #[allow(non_snake_case)]
 struct CountryConstructor {
     isoCode: String,
-    name: Vec<CountryName>
+    name: Vec<CountryName>,
 }
 
 type SubdivisionMap = (rust_iso3166::CountryCode, Option<HashMap<String, String>>);
 
-fn convert_country(constructor: &CountryConstructor) -> (String, SubdivisionMap) {
-    let iso_code = rust_iso3166::from_alpha2(&constructor.isoCode).unwrap(); 
-    let name = constructor.name.first().unwrap().text.to_owned();
-    let subdivision_map: Option<HashMap<String, String>> = iso_code
-        .subdivisions()
-        .map(|subdivision_list|
+fn convert_country(
+    constructor: &CountryConstructor,
+) -> Result<(String, SubdivisionMap), HolidayError> {
+    let iso_code = rust_iso3166::from_alpha2(&constructor.isoCode)
+        .ok_or(HolidayError::UnknownCountry(constructor.isoCode.clone()))?;
+    let name = constructor
+        .name
+        .first()
+        .ok_or(HolidayError::MissingCountryName(
+            constructor.isoCode.clone(),
+        ))?
+        .text
+        .to_owned();
+    let subdivision_map: Option<HashMap<String, String>> =
+        iso_code.subdivisions().map(|subdivision_list| {
             subdivision_list
                 .iter()
                 .map(|subdivision| (subdivision.name.to_string(), subdivision.code.to_string()))
                 .collect()
-        );
-    (name, (iso_code, subdivision_map))
+        });
+    Ok((name, (iso_code, subdivision_map)))
 }
 
-fn convert_holiday(constructor: &HolidayConstructor) -> Holiday {
-    let date = LocalDate::from_str(&constructor.startDate).unwrap();
-    let name = constructor.name.first().unwrap().text.to_owned();
-    Holiday { date, name }
+// @param locale the name in this language is used if there is one, the first name otherwise
+fn convert_holiday(
+    constructor: &HolidayConstructor,
+    locale: Locale,
+) -> Result<Holiday, HolidayError> {
+    let date = LocalDate::from_str(&constructor.startDate)
+        .map_err(|_| HolidayError::InvalidDate(constructor.startDate.clone()))?;
+    let name = constructor
+        .name
+        .iter()
+        .find(|name| name.language.as_deref() == Some(locale.iso_code()))
+        .or(constructor.name.first())
+        .ok_or(HolidayError::MissingName(constructor.startDate.clone()))?
+        .text
+        .to_owned();
+    Ok(Holiday { date, name })
 }
 
-pub async fn get_holidays(
-        year: u16, 
-        country_code: String, 
-        division_code: String) -> reqwest::Result<BTreeMap<LocalDate, String>> {
+async fn fetch_json<T: DeserializeOwned>(url: &str) -> Result<T, HolidayError> {
+    let response = reqwest::get(url).await.map_err(HolidayError::Network)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(HolidayError::Status(status));
+    }
+    let body = response.text().await.map_err(HolidayError::Network)?;
+    serde_json::from_str(&body).map_err(HolidayError::Schema)
+}
 
+pub async fn get_holidays(
+    year: u16,
+    country_code: String,
+    division_code: String,
+    locale: Locale,
+) -> Result<BTreeMap<LocalDate, String>, HolidayError> {
     let valid_from = [&year.to_string(), "01-01"].join("-");
     let valid_to = [&year.to_string(), "12-31"].join("-");
-    let url = [ "https://openholidaysapi.org/PublicHolidays?languageIsoCode=DE&countryIsoCode="
-              , &country_code
-              , "&validFrom="
-              , &valid_from
-              ,"&validTo="
-              , &valid_to
-              ,"&subdivisionCode="
-              , &division_code].join("");
-    let holidays: BTreeMap<LocalDate, String> = reqwest::get(url)
-                .await.expect("Expected to get an output!")
-                .json::<Vec<HolidayConstructor>>()
-                .await.expect("Expected to get a list of json objects.")
-                .iter()
-                .map(convert_holiday)
-                .map(|holiday| (holiday.date, holiday.name))
-                .collect();
+    let url = [
+        "https://openholidaysapi.org/PublicHolidays?languageIsoCode=",
+        locale.iso_code(),
+        "&countryIsoCode=",
+        &country_code,
+        "&validFrom=",
+        &valid_from,
+        "&validTo=",
+        &valid_to,
+        "&subdivisionCode=",
+        &division_code,
+    ]
+    .join("");
+    fetch_json::<Vec<HolidayConstructor>>(&url)
+        .await?
+        .iter()
+        .map(|constructor| {
+            convert_holiday(constructor, locale).map(|holiday| (holiday.date, holiday.name))
+        })
+        .collect()
+}
+
+// Uses a cached copy unless it is expired or `refresh` is set.
+// If the download fails any cached copy is used, even an expired one.
+pub async fn get_cached_holidays(
+    cache: &HolidayCache,
+    year: u16,
+    country_code: String,
+    division_code: String,
+    locale: Locale,
+    refresh: bool,
+) -> Result<BTreeMap<LocalDate, String>, HolidayError> {
+    let cached = cache.read(year, &country_code, &division_code, locale);
+    if let Some(cached) = &cached {
+        if !refresh && !cached.is_expired {
+            return Ok(cached.holidays.clone());
+        }
+    }
+    match get_holidays(year, country_code.clone(), division_code.clone(), locale).await {
+        Ok(holidays) => {
+            // NOTE: a failing cache must not prevent the plan generation
+            cache
+                .write(year, &country_code, &division_code, locale, &holidays)
+                .ok();
+            Ok(holidays)
+        }
+        Err(err) => cached.map(|cached| cached.holidays).ok_or(err),
+    }
+}
 
-    reqwest::Result::Ok(holidays)
+async fn fetch_holidays(
+    cache: Option<&HolidayCache>,
+    year: u16,
+    country_code: String,
+    division_code: String,
+    locale: Locale,
+    refresh: bool,
+) -> Result<BTreeMap<LocalDate, String>, HolidayError> {
+    match cache {
+        Some(cache) => {
+            get_cached_holidays(cache, year, country_code, division_code, locale, refresh).await
+        }
+        None => get_holidays(year, country_code, division_code, locale).await,
+    }
 }
 
-pub async fn get_countries() -> reqwest::Result<BTreeMap<String, SubdivisionMap>> {
+// @param refresh download the holidays even if a valid cached copy exists
+pub async fn load_holidays(
+    year: u16,
+    country_code: String,
+    division_code: String,
+    locale: Locale,
+    source: HolidaySource,
+    cache: Option<&HolidayCache>,
+    refresh: bool,
+) -> Result<BTreeMap<LocalDate, String>, HolidayError> {
+    if source == HolidaySource::Offline {
+        if let Some(holidays) =
+            holiday_calendar::compute_holidays(year, &country_code, &division_code, locale)
+        {
+            return Ok(holidays);
+        }
+    }
+    fetch_holidays(cache, year, country_code, division_code, locale, refresh).await
+}
+
+// @return the holidays of every year from first_year to last_year
+#[allow(clippy::too_many_arguments)]
+pub async fn load_holidays_for_years(
+    first_year: u16,
+    last_year: u16,
+    country_code: String,
+    division_code: String,
+    locale: Locale,
+    source: HolidaySource,
+    cache: Option<&HolidayCache>,
+    refresh: bool,
+) -> Result<BTreeMap<LocalDate, String>, HolidayError> {
+    let mut holidays = BTreeMap::new();
+    for year in first_year..=last_year {
+        holidays.extend(
+            load_holidays(
+                year,
+                country_code.clone(),
+                division_code.clone(),
+                locale,
+                source,
+                cache,
+                refresh,
+            )
+            .await?,
+        );
+    }
+    Ok(holidays)
+}
+
+// @return dates which are only a holiday in one of the maps, holidays with differing names are ignored
+pub fn compare_holidays(
+    offline: &BTreeMap<LocalDate, String>,
+    online: &BTreeMap<LocalDate, String>,
+) -> Vec<HolidayDifference> {
+    let only_offline = offline
+        .iter()
+        .filter(|(date, _)| !online.contains_key(date))
+        .map(|(date, name)| HolidayDifference {
+            date: *date,
+            offline: Some(name.to_owned()),
+            online: None,
+        });
+    let only_online = online
+        .iter()
+        .filter(|(date, _)| !offline.contains_key(date))
+        .map(|(date, name)| HolidayDifference {
+            date: *date,
+            offline: None,
+            online: Some(name.to_owned()),
+        });
+    let mut differences: Vec<HolidayDifference> = only_offline.chain(only_online).collect();
+    differences.sort_by_key(|difference| difference.date);
+    differences
+}
+
+// @return None if the country isn't supported by the offline calendar
+pub async fn cross_check_holidays(
+    year: u16,
+    country_code: String,
+    division_code: String,
+    locale: Locale,
+    cache: Option<&HolidayCache>,
+) -> Result<Option<Vec<HolidayDifference>>, HolidayError> {
+    let offline =
+        match holiday_calendar::compute_holidays(year, &country_code, &division_code, locale) {
+            Some(holidays) => holidays,
+            None => return Ok(None),
+        };
+    let online = fetch_holidays(cache, year, country_code, division_code, locale, false).await?;
+    Ok(Some(compare_holidays(&offline, &online)))
+}
+
+pub async fn get_countries() -> Result<BTreeMap<String, SubdivisionMap>, HolidayError> {
     let url = "https://openholidaysapi.org/Countries?languageIsoCode=DE";
-    let countries: BTreeMap<String, SubdivisionMap> = reqwest::get(url)
-                .await.expect("Expected to get an output!")
-                .json::<Vec<CountryConstructor>>()
-                .await.expect("Expected to get a list of json objects.")
-                .iter()
-                .map(convert_country)
-                .collect();
-    reqwest::Result::Ok(countries)
+    fetch_json::<Vec<CountryConstructor>>(url)
+        .await?
+        .iter()
+        .map(convert_country)
+        .collect()
 }
 
 #[test]
@@ -98,7 +323,61 @@ pub fn read_holidays_from_file() {
     // Open the file in read-only mode with buffer.
     let file = std::fs::File::open("./resources/testJSON.json").unwrap();
     let reader = std::io::BufReader::new(file);
-    let u: Vec<HolidayConstructor> = serde_json::from_reader(reader).expect("Possible to be parsed.");
-    let holidays: Vec<Holiday> = u.iter().map(|x| convert_holiday(x)).collect();
+    let u: Vec<HolidayConstructor> =
+        serde_json::from_reader(reader).expect("Possible to be parsed.");
+    let holidays: Vec<Holiday> = u
+        .iter()
+        .map(|constructor| convert_holiday(constructor, Locale::De))
+        .collect::<Result<_, _>>()
+        .expect("Possible to be converted.");
     print!("{:?}", holidays);
 }
+
+#[test]
+pub fn convert_holiday_names() {
+    let convert = |json: &str, locale: Locale| {
+        let constructor: HolidayConstructor = serde_json::from_str(json).unwrap();
+        convert_holiday(&constructor, locale)
+    };
+    let json = r#"{"startDate": "2025-08-01", "name": [
+        {"language": "DE", "text": "Nationalfeiertag"},
+        {"language": "FR", "text": "Fête nationale"}]}"#;
+
+    let holiday = convert(json, Locale::Fr).unwrap();
+    assert_eq!(
+        holiday.date,
+        LocalDate::ymd(2025, datetime::Month::August, 1).unwrap()
+    );
+    assert_eq!(holiday.name, "Fête nationale");
+    // no name in italian, the first one is used
+    assert_eq!(convert(json, Locale::It).unwrap().name, "Nationalfeiertag");
+    assert!(matches!(
+        convert(r#"{"startDate": "2025-08-01", "name": []}"#, Locale::De),
+        Err(HolidayError::MissingName(date)) if date == "2025-08-01"
+    ));
+    assert!(matches!(
+        convert(r#"{"startDate": "2025-13-01", "name": [{"text": "Feiertag"}]}"#, Locale::De),
+        Err(HolidayError::InvalidDate(date)) if date == "2025-13-01"
+    ));
+}
+
+#[test]
+pub fn convert_country_names() {
+    let convert = |json: &str| {
+        let constructor: CountryConstructor = serde_json::from_str(json).unwrap();
+        convert_country(&constructor)
+    };
+
+    let (name, (_, subdivisions)) =
+        convert(r#"{"isoCode": "CH", "name": [{"text": "Schweiz"}]}"#).unwrap();
+    assert_eq!(name, "Schweiz");
+    assert_eq!(subdivisions.unwrap()["Zürich"], "CH-ZH");
+    assert!(matches!(
+        convert(r#"{"isoCode": "CH", "name": []}"#),
+        Err(HolidayError::MissingCountryName(code)) if code == "CH"
+    ));
+    assert!(matches!(
+        convert(r#"{"isoCode": "XX", "name": [{"text": "Nirgendwo"}]}"#),
+        Err(HolidayError::UnknownCountry(code)) if code == "XX"
+    ));
+}
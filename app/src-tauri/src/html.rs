@@ -1,93 +1,211 @@
-use crate::types::{self, DayHTMLData, YearMap};
-use build_html::{
-    self, Html, HtmlContainer, HtmlElement, HtmlPage, Table, TableCell, TableCellType, TableRow,
-};
-use datetime::Month;
-
-fn create_row(day_data: &DayHTMLData) -> TableRow {
-    let date = TableCell::default().with_raw(day_data.date.clone());
-    let day = TableCell::default().with_raw(day_data.day.clone());
-    let appartment = TableCell::default().with_raw(day_data.appartment.clone());
-
-    let day_attrs = if day_data.day == "So" {
-        "fs-7 day sunday"
-    } else {
-        "fs-7 day"
-    };
-
-    let date_attrs = if day_data.day == "So" {
-        "fs-7 date sunday"
-    } else {
-        "fs-7 date"
-    };
-
-    let row = TableRow::new()
-        .with_cell(date.with_attributes([("class", date_attrs)]))
-        .with_cell(day.with_attributes([("class", day_attrs)]))
-        .with_cell(appartment.with_attributes([("class", "fs-7 apartment")]));
-    if day_data.is_holiday {
-        row.with_attributes([("class", "table-info")])
-    } else {
-        row
+use crate::plan::{Plan, PlanDay};
+use crate::types::{self, DayHTMLData};
+use datetime::DatePiece;
+use minijinja::{context, Environment};
+use serde::Serialize;
+use std::{error::Error, fmt};
+
+// NOTE: reproduces the layout the plan had before templates were supported
+pub const DEFAULT_TEMPLATE: &str = include_str!("../resources/plan_template.html");
+
+const DEFAULT_TEMPLATE_NAME: &str = "default.html";
+const CUSTOM_TEMPLATE_NAME: &str = "custom.html";
+
+#[derive(Debug)]
+pub enum TemplateError {
+    // @param path of the template file
+    Read(String, std::io::Error),
+    Render(minijinja::Error),
+}
+
+impl Error for TemplateError {}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Read(path, err) => write!(f, "Could not read the template '{}': {}", path, err),
+            Self::Render(err) => write!(f, "Could not render the template: {}", err),
+        }
     }
 }
 
-fn create_month_table(day_data: &[DayHTMLData]) -> Table {
-    day_data.iter().fold(Table::new(), |table, day_data| {
-        table.with_custom_body_row(create_row(day_data))
-    })
+#[derive(Serialize)]
+struct MonthTemplateData {
+    title: String,
+    days: Vec<DayHTMLData>,
+}
+
+#[derive(Serialize)]
+struct HolidayTemplateData {
+    date: String,
+    name: String,
+}
+
+// NOTE: names and contact details are left out if the config hides them
+#[derive(Serialize)]
+struct ApartmentTemplateData {
+    label: String,
+    door: Option<String>,
+    tenant: Option<String>,
+    email: Option<String>,
+    phone: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PageTemplateData<'a> {
+    rows: Vec<&'a [MonthTemplateData]>,
+}
+
+fn create_month_data(config: &types::Config, plan: &Plan) -> Vec<MonthTemplateData> {
+    plan.months()
+        .iter()
+        .map(|days| MonthTemplateData {
+            title: plan.print_month(days, config.locale),
+            days: days
+                .iter()
+                .map(|day| day.create_html_data(config))
+                .collect(),
+        })
+        .collect()
 }
 
-fn create_month_row(row_data: &[(&i8, &Vec<DayHTMLData>)]) -> TableRow {
-    assert!(row_data.len() == 3, "Wrong number of months");
-
-    row_data.iter().fold(TableRow::new(), |row, (month, data)| {
-        let current_month = Month::from_zero(**month).unwrap();
-        row.with_cell(
-            TableCell::new(TableCellType::Data).with_table(
-                create_month_table(data)
-                    .with_attributes([("class", "table table-sm table-striped striped")])
-                    .with_custom_header_row(
-                        TableRow::new().with_cell(
-                            TableCell::new(TableCellType::Header)
-                                .with_raw(types::month_to_string(current_month))
-                                .with_attributes([("class", "fs-7"), ("colspan", "3")]),
-                        ),
-                    ),
-            ),
-        )
-    })
+// @param months the months of the plan as returned by `create_month_data`
+fn create_page_data<'a>(
+    config: &types::Config,
+    plan: &Plan,
+    months: &'a [MonthTemplateData],
+) -> Vec<PageTemplateData<'a>> {
+    let mut remaining = months;
+    config
+        .layout
+        .pages(plan)
+        .iter()
+        .map(|page| {
+            let (page_months, rest) = remaining.split_at(page.len());
+            remaining = rest;
+            PageTemplateData {
+                rows: page_months.chunks(config.layout.months_per_row).collect(),
+            }
+        })
+        .collect()
 }
 
-pub fn create_year_html(config: &types::Config, year_map: &YearMap, year: u16) -> String {
-    let YearMap(map) = year_map;
-    let mut month_data_vec: Vec<(&i8, &Vec<DayHTMLData>)> = map.iter().collect();
-    month_data_vec.sort_by_key(|(y, _)| **y);
-    let month_triples: Vec<Vec<(&i8, &Vec<DayHTMLData>)>> =
-        month_data_vec.chunks(3).map(|x| x.to_vec()).collect();
+fn create_apartment_data(config: &types::Config) -> Vec<ApartmentTemplateData> {
+    let private = |value: &Option<String>| value.clone().filter(|_| !config.hide_names);
+    config
+        .apartments
+        .iter()
+        .filter_map(|metadata| {
+            Some(ApartmentTemplateData {
+                label: config.apartment_id(metadata)?.print(config),
+                door: metadata.door.clone(),
+                tenant: private(&metadata.tenant),
+                email: private(&metadata.email),
+                phone: private(&metadata.phone),
+            })
+        })
+        .collect()
+}
 
-    let table = month_triples
+// @param name the holiday or the blackout of a day
+fn create_holiday_data(
+    plan: &Plan,
+    name: impl Fn(&PlanDay) -> &Option<String>,
+) -> Vec<HolidayTemplateData> {
+    plan.days
         .iter()
-        .fold(Table::new(), |table, triple| {
-            table.with_custom_body_row(create_month_row(triple))
+        .filter_map(|day| {
+            name(day).as_ref().map(|name| HolidayTemplateData {
+                date: types::print_full_local_date(day.date),
+                name: name.clone(),
+            })
+        })
+        .collect()
+}
+
+// @return the template file set in the config, the default template otherwise
+pub fn read_template(config: &types::Config) -> Result<String, TemplateError> {
+    match &config.template {
+        Some(path) => {
+            std::fs::read_to_string(path).map_err(|err| TemplateError::Read(path.clone(), err))
+        }
+        None => Ok(DEFAULT_TEMPLATE.to_owned()),
+    }
+}
+
+// The template sees the following variables:
+// title, period (year or first and last day), year (of the first day), locale (ISO code),
+// months (title and days of every month), rows (the months in rows as set by the layout),
+// pages (the rows of every page), layout, landscape (whether the pages are printed landscape),
+// slots (the labels of the slots of a day), slot_count (at least one),
+// apartments (label, door, tenant, email and phone), holidays and blackouts (date and name)
+// and plan (the structured plan as in the JSON export)
+// NOTE: a template may extend "default.html" and only override its blocks head, header or footer
+pub fn render_plan(
+    template: &str,
+    config: &types::Config,
+    plan: &Plan,
+) -> Result<String, TemplateError> {
+    let mut environment = Environment::new();
+    environment
+        .add_template(DEFAULT_TEMPLATE_NAME, DEFAULT_TEMPLATE)
+        .map_err(TemplateError::Render)?;
+    environment
+        .add_template(CUSTOM_TEMPLATE_NAME, template)
+        .map_err(TemplateError::Render)?;
+    let months = create_month_data(config, plan);
+    let rows: Vec<&[MonthTemplateData]> = months.chunks(config.layout.months_per_row).collect();
+    let pages = create_page_data(config, plan, &months);
+    environment
+        .get_template(CUSTOM_TEMPLATE_NAME)
+        .and_then(|template| {
+            template.render(context! {
+                title => config.title,
+                period => plan.print_period(),
+                year => plan.start.year(),
+                locale => config.locale.iso_code(),
+                rows => rows,
+                pages => pages,
+                layout => config.layout,
+                landscape => config.layout.is_landscape(),
+                slots => config.slots,
+                slot_count => config.slot_count(),
+                months => months,
+                apartments => create_apartment_data(config),
+                holidays => create_holiday_data(plan, |day| &day.holiday),
+                blackouts => create_holiday_data(plan, |day| &day.blackout),
+                plan => plan,
+            })
         })
-        .with_attributes([("class", "table table-sm table-borderless mb-0")]);
-    HtmlPage::new()
-        .with_html(
-            HtmlElement::new(build_html::HtmlTag::Heading5)
-                .with_child(
-                    HtmlElement::new(build_html::HtmlTag::Span)
-                        .with_attribute("id", "house-name")
-                        .with_raw(config.title.clone())
-                        .into(),
-                )
-                .with_child(
-                    HtmlElement::new(build_html::HtmlTag::Span)
-                        .with_attribute("id", "year")
-                        .with_raw(year)
-                        .into(),
-                ),
-        )
-        .with_table(table)
-        .to_html_string()
+        .map_err(TemplateError::Render)
+}
+
+pub fn create_year_html(config: &types::Config, plan: &Plan) -> Result<String, TemplateError> {
+    render_plan(&read_template(config)?, config, plan)
+}
+
+#[test]
+pub fn extend_default_template() {
+    let config = types::sample_config();
+    let plan = types::create_full_year(
+        &config,
+        2025,
+        &types::sample_state(),
+        false,
+        &std::collections::BTreeMap::new(),
+    )
+    .unwrap();
+    let default = render_plan(DEFAULT_TEMPLATE, &config, &plan).unwrap();
+    let custom = render_plan(
+        "{% extends \"default.html\" %}{% block footer %}<footer>{{ title }}</footer>{% endblock %}",
+        &config,
+        &plan,
+    )
+    .unwrap();
+
+    assert!(default.contains("<span id=\"year\">2025</span>"));
+    assert_eq!(
+        custom.replace(&format!("<footer>{}</footer>", config.title), ""),
+        default
+    );
 }
@@ -0,0 +1,356 @@
+use crate::plan::Weekday;
+use datetime::Month;
+use serde::{Deserialize, Serialize};
+
+// Language of the labels in the plan and of the holiday names.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    De,
+    Fr,
+    It,
+    En,
+}
+
+// German name as used by `holiday_calendar` and its translations
+// (german, french, italian, english)
+const HOLIDAY_NAMES: &[(&str, &str, &str, &str)] = &[
+    ("Neujahr", "Nouvel An", "Capodanno", "New Year's Day"),
+    ("Neujahrstag", "Nouvel An", "Capodanno", "New Year's Day"),
+    (
+        "Berchtoldstag",
+        "Saint-Berchtold",
+        "San Bertoldo",
+        "Berchtold's Day",
+    ),
+    ("Heilige Drei Könige", "Épiphanie", "Epifania", "Epiphany"),
+    (
+        "Internationaler Frauentag",
+        "Journée internationale des femmes",
+        "Giornata internazionale della donna",
+        "International Women's Day",
+    ),
+    (
+        "Ausrufung der Republik",
+        "Instauration de la République",
+        "Instaurazione della Repubblica",
+        "Republic Day",
+    ),
+    (
+        "Josephstag",
+        "Saint-Joseph",
+        "San Giuseppe",
+        "Saint Joseph's Day",
+    ),
+    (
+        "Näfelser Fahrt",
+        "Fête de Näfels",
+        "Festa di Näfels",
+        "Näfels Pilgrimage",
+    ),
+    (
+        "Karfreitag",
+        "Vendredi saint",
+        "Venerdì santo",
+        "Good Friday",
+    ),
+    ("Ostersonntag", "Pâques", "Pasqua", "Easter Sunday"),
+    (
+        "Ostermontag",
+        "Lundi de Pâques",
+        "Lunedì di Pasqua",
+        "Easter Monday",
+    ),
+    (
+        "Tag der Arbeit",
+        "Fête du travail",
+        "Festa del lavoro",
+        "Labour Day",
+    ),
+    (
+        "Staatsfeiertag",
+        "Fête nationale",
+        "Festa nazionale",
+        "National Holiday",
+    ),
+    ("Auffahrt", "Ascension", "Ascensione", "Ascension Day"),
+    (
+        "Christi Himmelfahrt",
+        "Ascension",
+        "Ascensione",
+        "Ascension Day",
+    ),
+    ("Pfingstsonntag", "Pentecôte", "Pentecoste", "Whit Sunday"),
+    (
+        "Pfingstmontag",
+        "Lundi de Pentecôte",
+        "Lunedì di Pentecoste",
+        "Whit Monday",
+    ),
+    (
+        "Fronleichnam",
+        "Fête-Dieu",
+        "Corpus Domini",
+        "Corpus Christi",
+    ),
+    (
+        "Jahrestag der Jura-Abstimmung",
+        "Commémoration du plébiscite jurassien",
+        "Commemorazione del plebiscito giurassiano",
+        "Jura Plebiscite Day",
+    ),
+    (
+        "Peter und Paul",
+        "Saint-Pierre et Saint-Paul",
+        "Santi Pietro e Paolo",
+        "Saints Peter and Paul",
+    ),
+    (
+        "Bundesfeiertag",
+        "Fête nationale",
+        "Festa nazionale",
+        "Swiss National Day",
+    ),
+    (
+        "Mariä Himmelfahrt",
+        "Assomption",
+        "Assunzione",
+        "Assumption Day",
+    ),
+    (
+        "Weltkindertag",
+        "Journée mondiale de l'enfance",
+        "Giornata mondiale dell'infanzia",
+        "World Children's Day",
+    ),
+    (
+        "Eidgenössischer Dank-, Buss- und Bettag",
+        "Jeûne fédéral",
+        "Digiuno federale",
+        "Federal Day of Thanksgiving",
+    ),
+    (
+        "Bettagsmontag",
+        "Lundi du Jeûne fédéral",
+        "Lunedì del Digiuno federale",
+        "Federal Fast Monday",
+    ),
+    (
+        "Genfer Bettag",
+        "Jeûne genevois",
+        "Digiuno ginevrino",
+        "Geneva Fast",
+    ),
+    (
+        "Bruder Klaus",
+        "Saint Nicolas de Flüe",
+        "San Nicolao della Flüe",
+        "Saint Nicholas of Flüe",
+    ),
+    (
+        "Tag der Deutschen Einheit",
+        "Jour de l'Unité allemande",
+        "Giorno dell'unità tedesca",
+        "German Unity Day",
+    ),
+    (
+        "Nationalfeiertag",
+        "Fête nationale",
+        "Festa nazionale",
+        "National Day",
+    ),
+    (
+        "Reformationstag",
+        "Fête de la Réforme",
+        "Festa della Riforma",
+        "Reformation Day",
+    ),
+    (
+        "Allerheiligen",
+        "Toussaint",
+        "Ognissanti",
+        "All Saints' Day",
+    ),
+    (
+        "Buß- und Bettag",
+        "Jour de repentance et de prière",
+        "Giorno di penitenza e preghiera",
+        "Day of Repentance and Prayer",
+    ),
+    (
+        "Mariä Empfängnis",
+        "Immaculée Conception",
+        "Immacolata Concezione",
+        "Immaculate Conception",
+    ),
+    ("Weihnachten", "Noël", "Natale", "Christmas Day"),
+    ("Christtag", "Noël", "Natale", "Christmas Day"),
+    (
+        "Stephanstag",
+        "Saint-Étienne",
+        "Santo Stefano",
+        "St. Stephen's Day",
+    ),
+    (
+        "Stefanitag",
+        "Saint-Étienne",
+        "Santo Stefano",
+        "St. Stephen's Day",
+    ),
+    (
+        "Restauration der Republik",
+        "Restauration de la République",
+        "Restaurazione della Repubblica",
+        "Restoration of the Republic",
+    ),
+];
+
+impl Locale {
+    // @return ISO 639-1 code as expected by openholidaysapi.org
+    pub fn iso_code(&self) -> &'static str {
+        match self {
+            Self::De => "DE",
+            Self::Fr => "FR",
+            Self::It => "IT",
+            Self::En => "EN",
+        }
+    }
+
+    // @return two letter abbreviation of the weekday
+    pub fn weekday(&self, weekday: Weekday) -> &'static str {
+        let names = match self {
+            Self::De => ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+            Self::Fr => ["Lu", "Ma", "Me", "Je", "Ve", "Sa", "Di"],
+            Self::It => ["Lu", "Ma", "Me", "Gi", "Ve", "Sa", "Do"],
+            Self::En => ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"],
+        };
+        names[weekday as usize]
+    }
+
+    pub fn month(&self, month: Month) -> &'static str {
+        let names = match self {
+            Self::De => [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            Self::Fr => [
+                "Janvier",
+                "Février",
+                "Mars",
+                "Avril",
+                "Mai",
+                "Juin",
+                "Juillet",
+                "Août",
+                "Septembre",
+                "Octobre",
+                "Novembre",
+                "Décembre",
+            ],
+            Self::It => [
+                "Gennaio",
+                "Febbraio",
+                "Marzo",
+                "Aprile",
+                "Maggio",
+                "Giugno",
+                "Luglio",
+                "Agosto",
+                "Settembre",
+                "Ottobre",
+                "Novembre",
+                "Dicembre",
+            ],
+            Self::En => [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ],
+        };
+        names[month.months_from_january()]
+    }
+
+    // @return names of the left, middle and right apartment
+    pub fn positions(&self) -> [&'static str; 3] {
+        match self {
+            Self::De => ["Links", "Mitte", "Rechts"],
+            Self::Fr => ["Gauche", "Milieu", "Droite"],
+            Self::It => ["Sinistra", "Centro", "Destra"],
+            Self::En => ["Left", "Middle", "Right"],
+        }
+    }
+
+    // @return abbreviation of the ground floor
+    pub fn ground_floor(&self) -> &'static str {
+        match self {
+            Self::De => "P",
+            Self::Fr => "R",
+            Self::It => "PT",
+            Self::En => "G",
+        }
+    }
+
+    pub fn laundry_day(&self) -> &'static str {
+        match self {
+            Self::De => "Waschtag",
+            Self::Fr => "Jour de lessive",
+            Self::It => "Giorno di bucato",
+            Self::En => "Laundry day",
+        }
+    }
+
+    // @return label of a day nobody washes on
+    pub fn free(&self) -> &'static str {
+        match self {
+            Self::De => "frei",
+            Self::Fr => "libre",
+            Self::It => "libero",
+            Self::En => "free",
+        }
+    }
+
+    // @param name german name of a holiday computed by `holiday_calendar`
+    // @return the german name if there is no translation
+    pub fn holiday(&self, name: &str) -> String {
+        HOLIDAY_NAMES
+            .iter()
+            .find(|(german, _, _, _)| *german == name)
+            .map(|(german, french, italian, english)| match self {
+                Self::De => *german,
+                Self::Fr => *french,
+                Self::It => *italian,
+                Self::En => *english,
+            })
+            .unwrap_or(name)
+            .to_owned()
+    }
+}
+
+#[test]
+pub fn translate_labels() {
+    assert_eq!(Locale::Fr.weekday(Weekday::Sunday), "Di");
+    assert_eq!(Locale::It.month(Month::December), "Dicembre");
+    assert_eq!(Locale::En.holiday("Auffahrt"), "Ascension Day");
+    assert_eq!(Locale::De.holiday("Genfer Bettag"), "Genfer Bettag");
+    assert_eq!(Locale::Fr.holiday("Genfer Bettag"), "Jeûne genevois");
+    assert_eq!(Locale::Fr.holiday("Unbekannt"), "Unbekannt");
+}
@@ -0,0 +1,194 @@
+use crate::plan::{ApartmentId, Plan};
+use crate::types;
+use datetime::{DatePiece, LocalDate, LocalDateTime, TimePiece};
+
+// RFC 5545 limits content lines to 75 octets
+const MAX_LINE_LENGTH: usize = 75;
+
+pub struct ApartmentCalendar {
+    pub apartment: ApartmentId,
+    pub label: String,
+    pub content: String,
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// continuation lines start with a space which counts towards the limit
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for char in line.chars() {
+        if length + char.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(char);
+        length += char.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+fn print_ics_date(date: LocalDate) -> String {
+    format!(
+        "{:04}{:02}{:02}",
+        date.year(),
+        date.month().months_from_january() + 1,
+        date.day()
+    )
+}
+
+// @return current UTC time, e.g. 20250101T120000Z
+fn print_timestamp() -> String {
+    let now = LocalDateTime::now();
+    format!(
+        "{}T{:02}{:02}{:02}Z",
+        print_ics_date(now.date()),
+        now.hour(),
+        now.minute(),
+        now.second()
+    )
+}
+
+// UIDs have to be globally unique, the plans of two buildings must not overwrite each other
+// @return FNV-1a hash of the title, stable across versions and platforms
+fn building_id(title: &str) -> String {
+    let hash = title.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+// @param building see `building_id`
+fn create_event(
+    uid: &str,
+    building: &str,
+    timestamp: &str,
+    date: LocalDate,
+    summary: &str,
+) -> Vec<String> {
+    let end = types::next_date(date);
+    vec![
+        "BEGIN:VEVENT".to_owned(),
+        format!("UID:{}-{}@waschplan", uid, building),
+        format!("DTSTAMP:{}", timestamp),
+        format!("DTSTART;VALUE=DATE:{}", print_ics_date(date)),
+        format!("DTEND;VALUE=DATE:{}", print_ics_date(end)),
+        format!("SUMMARY:{}", escape_text(summary)),
+        "TRANSP:TRANSPARENT".to_owned(),
+        "END:VEVENT".to_owned(),
+    ]
+}
+
+// @param apartment only the laundry days of this apartment are added if given
+pub fn create_calendar(
+    config: &types::Config,
+    plan: &Plan,
+    apartment: Option<ApartmentId>,
+) -> String {
+    let timestamp = print_timestamp();
+    let building = building_id(&config.title);
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        format!(
+            "PRODID:-//waschplan//waschplan//{}",
+            config.locale.iso_code()
+        ),
+        "CALSCALE:GREGORIAN".to_owned(),
+        format!("X-WR-CALNAME:{}", escape_text(&config.title)),
+    ];
+
+    plan.days.iter().for_each(|day| {
+        if let Some(name) = &day.holiday {
+            let uid = format!("{}-holiday", print_ics_date(day.date));
+            lines.extend(create_event(&uid, &building, &timestamp, day.date, name));
+        }
+        if let Some(name) = &day.blackout {
+            let uid = format!("{}-blackout", print_ics_date(day.date));
+            lines.extend(create_event(&uid, &building, &timestamp, day.date, name));
+        }
+        for slot in 0..day.assignments.len() {
+            let Some(id) = day.apartment(slot) else {
+                continue;
+            };
+            if apartment.is_some_and(|apartment| apartment != id) {
+                continue;
+            }
+            let mut uid = format!("{}-{}-{}", print_ics_date(day.date), id.floor, id.unit);
+            let mut summary = format!("{} {}", config.locale.laundry_day(), id.print(config));
+            // NOTE: an apartment may wash in several slots of a day
+            if let Some(label) = config.slot_label(slot) {
+                uid = format!("{}-{}", uid, slot);
+                summary = format!("{} ({})", summary, label);
+            }
+            lines.extend(create_event(
+                &uid, &building, &timestamp, day.date, &summary,
+            ));
+        }
+    });
+
+    lines.push("END:VCALENDAR".to_owned());
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+// @return one calendar per apartment ordered by floor and position
+pub fn create_apartment_calendars(config: &types::Config, plan: &Plan) -> Vec<ApartmentCalendar> {
+    config
+        .apartment_ids()
+        .into_iter()
+        .map(|apartment| ApartmentCalendar {
+            apartment,
+            label: apartment.print(config),
+            content: create_calendar(config, plan, Some(apartment)),
+        })
+        .collect()
+}
+
+#[test]
+pub fn fold_long_lines() {
+    let line = format!("SUMMARY:{}", "ä".repeat(40));
+    let folded = fold_line(&line);
+    assert!(folded
+        .split("\r\n")
+        .all(|part| part.len() <= MAX_LINE_LENGTH));
+    assert_eq!(folded.replace("\r\n ", "").trim_end(), line);
+}
+
+#[test]
+pub fn separate_buildings() {
+    let mut config = types::sample_config();
+    let mut uids = |title: &str| -> Vec<String> {
+        config.title = title.to_string();
+        let new_year = LocalDate::ymd(2025, datetime::Month::January, 1).unwrap();
+        let holidays = std::collections::BTreeMap::from([(new_year, "Neujahr".to_string())]);
+        let plan = types::create_full_year(&config, 2025, &types::sample_state(), true, &holidays)
+            .unwrap();
+        create_calendar(&config, &plan, None)
+            .lines()
+            .filter(|line| line.starts_with("UID:"))
+            .map(str::to_owned)
+            .collect()
+    };
+
+    let first = uids("Haus A");
+    let second = uids("Haus B");
+    assert_eq!(first, uids("Haus A"));
+    assert_eq!(first.len(), second.len());
+    assert!(first.iter().all(|uid| !second.contains(uid)));
+}
+
+#[test]
+pub fn end_event_after_leap_year() {
+    let config = types::sample_config();
+    let plan = types::sample_plan(&config, 2032);
+    let calendar = create_calendar(&config, &plan, None);
+
+    // 31.12.2032 is a Friday
+    assert!(calendar.contains("DTSTART;VALUE=DATE:20321231\r\nDTEND;VALUE=DATE:20330101\r\n"));
+}
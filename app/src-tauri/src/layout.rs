@@ -0,0 +1,105 @@
+use crate::plan::{Plan, PlanDay};
+use datetime::DatePiece;
+use serde::{Deserialize, Serialize};
+
+// the months of a year have to fill every row
+pub const VALID_MONTHS_PER_ROW: [usize; 5] = [1, 2, 3, 4, 6];
+
+// where the plan starts a new page
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PageSplit {
+    // only starts a new page if the current one is full
+    #[default]
+    Continuous,
+    HalfYear,
+    Quarter,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Orientation {
+    #[default]
+    Portrait,
+    Landscape,
+}
+
+// arrangement of the months on the printed plan, the default is four rows of three months
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct Layout {
+    pub months_per_row: usize,
+    pub page_split: PageSplit,
+    pub orientation: Orientation,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout {
+            months_per_row: 3,
+            page_split: PageSplit::Continuous,
+            orientation: Orientation::Portrait,
+        }
+    }
+}
+
+impl PageSplit {
+    // @return the number of months per page or None if the pages aren't split
+    pub fn months_per_page(&self) -> Option<usize> {
+        match self {
+            Self::Continuous => None,
+            Self::HalfYear => Some(6),
+            Self::Quarter => Some(3),
+        }
+    }
+}
+
+impl Layout {
+    pub fn is_valid(&self) -> bool {
+        VALID_MONTHS_PER_ROW.contains(&self.months_per_row)
+    }
+
+    // @return the months of every page, a continuous plan is a single page
+    // NOTE: pages follow the calendar, a plan starting in May has a first quarter of two months
+    pub fn pages<'a>(&self, plan: &'a Plan) -> Vec<Vec<&'a [PlanDay]>> {
+        let months = plan.months();
+        let Some(months_per_page) = self.page_split.months_per_page() else {
+            return vec![months];
+        };
+        months
+            .chunk_by(|month, next| {
+                let (date, next) = (month[0].date, next[0].date);
+                date.year() == next.year()
+                    && date.month().months_from_january() / months_per_page
+                        == next.month().months_from_january() / months_per_page
+            })
+            .map(|page| page.to_vec())
+            .collect()
+    }
+
+    pub fn is_landscape(&self) -> bool {
+        self.orientation == Orientation::Landscape
+    }
+}
+
+#[test]
+pub fn split_pages_by_quarter() {
+    let config = crate::types::sample_config();
+    let start = datetime::LocalDate::ymd(2025, datetime::Month::May, 15).unwrap();
+    let end = datetime::LocalDate::ymd(2026, datetime::Month::January, 31).unwrap();
+    let plan = crate::types::create_plan(
+        &config,
+        start,
+        end,
+        &crate::types::sample_state(),
+        false,
+        &std::collections::BTreeMap::new(),
+    )
+    .unwrap();
+    let layout = Layout {
+        page_split: PageSplit::Quarter,
+        ..Layout::default()
+    };
+
+    let page_lengths: Vec<usize> = layout.pages(&plan).iter().map(|page| page.len()).collect();
+    assert_eq!(page_lengths, vec![2, 3, 3, 1]);
+    assert_eq!(Layout::default().pages(&plan).len(), 1);
+}
@@ -2,12 +2,24 @@ use datetime::{DatePiece, LocalDate};
 use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
+use std::time::Duration;
 use tauri::Manager;
 use tauri_plugin_dialog::DialogExt;
 
+pub mod export;
+pub mod fairness;
+pub mod holiday_cache;
+pub mod holiday_calendar;
 pub mod holidays;
 pub mod html;
+pub mod i18n;
+pub mod ics;
+pub mod layout;
+pub mod pdf;
+pub mod plan;
+pub mod stats;
 pub mod types;
+pub mod workspace;
 
 #[tauri::command]
 fn save_config(app: tauri::AppHandle, config: types::Config) {
@@ -59,65 +71,422 @@ fn localdate_to_string(date: &LocalDate) -> String {
     )
 }
 
+// @param max_age days after which cached holidays are downloaded again, the default if None
+fn holiday_cache(
+    app: &tauri::AppHandle,
+    max_age: Option<u64>,
+) -> Option<holiday_cache::HolidayCache> {
+    let cache = app
+        .path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| holiday_cache::HolidayCache::new(dir.join("holidays")))?;
+    Some(match max_age {
+        Some(days) => cache.with_max_age(Duration::from_secs(days * 24 * 60 * 60)),
+        None => cache,
+    })
+}
+
+// @param end_year also load the holidays of the following years up to end_year
+#[allow(clippy::too_many_arguments)]
+async fn load_holidays(
+    app: &tauri::AppHandle,
+    year: u16,
+    end_year: Option<u16>,
+    country_iso: String,
+    subdivision_iso: String,
+    locale: i18n::Locale,
+    source: holidays::HolidaySource,
+    refresh: bool,
+    cache_max_age: Option<u64>,
+) -> Result<Vec<(String, String)>, String> {
+    let cache = holiday_cache(app, cache_max_age);
+    holidays::load_holidays_for_years(
+        year,
+        end_year.unwrap_or(year),
+        country_iso,
+        subdivision_iso,
+        locale,
+        source,
+        cache.as_ref(),
+        refresh,
+    )
+    .await
+    .map(|holidays| {
+        holidays
+            .iter()
+            .map(|(date, holiday)| (localdate_to_string(date), holiday.to_owned()))
+            .collect()
+    })
+    .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn get_holidays(
+    app: tauri::AppHandle,
     year: u16,
+    end_year: Option<u16>,
     country_iso: String,
     subdivision_iso: String,
-) -> Vec<(String, String)> {
-    holidays::get_holidays(year, country_iso, subdivision_iso)
-        .await
-        .expect("Expected to get holidays")
-        .iter()
-        .map(|(date, holiday)| (localdate_to_string(date), holiday.to_owned()))
-        .collect()
+    locale: Option<i18n::Locale>,
+    source: Option<holidays::HolidaySource>,
+    cache_max_age: Option<u64>,
+) -> Result<Vec<(String, String)>, String> {
+    load_holidays(
+        &app,
+        year,
+        end_year,
+        country_iso,
+        subdivision_iso,
+        locale.unwrap_or_default(),
+        source.unwrap_or_default(),
+        false,
+        cache_max_age,
+    )
+    .await
+}
+
+// downloads the holidays again and replaces the cached copy
+#[tauri::command]
+async fn refresh_holidays(
+    app: tauri::AppHandle,
+    year: u16,
+    end_year: Option<u16>,
+    country_iso: String,
+    subdivision_iso: String,
+    locale: Option<i18n::Locale>,
+) -> Result<Vec<(String, String)>, String> {
+    load_holidays(
+        &app,
+        year,
+        end_year,
+        country_iso,
+        subdivision_iso,
+        locale.unwrap_or_default(),
+        holidays::HolidaySource::Online,
+        true,
+        None,
+    )
+    .await
 }
 
+// @return (date, offline name, online name) for every date the two holiday sources disagree on
 #[tauri::command]
-fn get_subdivisions(country_iso: &str) -> Vec<(String, String)> {
-    let iso = rust_iso3166::from_alpha2(country_iso).unwrap();
-    match iso.subdivisions() {
+async fn check_holidays(
+    app: tauri::AppHandle,
+    year: u16,
+    country_iso: String,
+    subdivision_iso: String,
+    locale: Option<i18n::Locale>,
+) -> Result<Vec<(String, Option<String>, Option<String>)>, String> {
+    let cache = holiday_cache(&app, None);
+    let differences = holidays::cross_check_holidays(
+        year,
+        country_iso,
+        subdivision_iso,
+        locale.unwrap_or_default(),
+        cache.as_ref(),
+    )
+    .await
+    .map_err(|err| err.to_string())?
+    .ok_or("No offline holidays available for this country.".to_owned())?;
+    Ok(differences
+        .into_iter()
+        .map(|difference| {
+            (
+                localdate_to_string(&difference.date),
+                difference.offline,
+                difference.online,
+            )
+        })
+        .collect())
+}
+
+#[tauri::command]
+fn get_subdivisions(country_iso: &str) -> Result<Vec<(String, String)>, String> {
+    let iso = rust_iso3166::from_alpha2(country_iso)
+        .ok_or(format!("Unknown country code '{}'.", country_iso))?;
+    Ok(match iso.subdivisions() {
         None => vec![],
         Some(subdivisions) => subdivisions
             .iter()
             .map(|subdivision| (subdivision.name.into(), subdivision.code.into()))
             .collect(),
-    }
+    })
 }
 
-fn string_to_local_date(date_str: &str) -> Option<LocalDate> {
-    let ymd: Vec<i64> = date_str
-        .split('.')
-        .filter_map(|char| char.parse().ok())
-        .collect();
-    match ymd.as_slice() {
-        [day, month_num, year] => datetime::Month::from_one(*month_num as i8)
-            .and_then(|month| LocalDate::ymd(*year, month, *day as i8))
-            .ok(),
-        _ => None,
-    }
+fn parse_holidays(
+    holidays: BTreeMap<String, String>,
+) -> Result<BTreeMap<LocalDate, String>, String> {
+    holidays
+        .into_iter()
+        .map(|(date, name)| match types::string_to_local_date(&date) {
+            Some(date) => Ok((date, name)),
+            None => Err(format!("Invalid holiday date '{}'.", date)),
+        })
+        .collect()
+}
+
+// @param period first and last day of the plan, e.g. ("1.4.2025", "31.3.2026"), the whole year if None
+fn create_plan(
+    config: &types::Config,
+    year: u16,
+    period: Option<(String, String)>,
+    apartment_info: &types::ApartmentInfo,
+    holidays: BTreeMap<String, String>,
+    exclude_sunday: bool,
+) -> Result<plan::Plan, String> {
+    let (start, end) = match period {
+        Some((start, end)) => (
+            types::string_to_local_date(&start).ok_or(format!("Invalid date '{}'.", start))?,
+            types::string_to_local_date(&end).ok_or(format!("Invalid date '{}'.", end))?,
+        ),
+        None => types::full_year(year),
+    };
+    let holidays = parse_holidays(holidays)?;
+    types::create_plan(
+        config,
+        start,
+        end,
+        apartment_info,
+        exclude_sunday,
+        &holidays,
+    )
+    .map_err(|err| err.to_string())
+}
+
+// @return the plan without any formatting so the frontend can process it
+#[tauri::command]
+fn get_laundry_plan(
+    config: types::Config,
+    year: u16,
+    period: Option<(String, String)>,
+    apartment_info: types::ApartmentInfo,
+    holidays: BTreeMap<String, String>,
+    exclude_sunday: bool,
+) -> Result<plan::Plan, String> {
+    create_plan(
+        &config,
+        year,
+        period,
+        &apartment_info,
+        holidays,
+        exclude_sunday,
+    )
+}
+
+// @return what every apartment gets in the plan, e.g. to justify the plan to the tenants
+#[tauri::command]
+fn get_plan_stats(
+    config: types::Config,
+    year: u16,
+    period: Option<(String, String)>,
+    apartment_info: types::ApartmentInfo,
+    holidays: BTreeMap<String, String>,
+    exclude_sunday: bool,
+) -> Result<Vec<stats::ApartmentStats>, String> {
+    let plan = create_plan(
+        &config,
+        year,
+        period,
+        &apartment_info,
+        holidays,
+        exclude_sunday,
+    )?;
+    Ok(stats::create_stats(&config, &plan))
 }
 
 #[tauri::command]
 fn create_laundry_plan(
     config: types::Config,
     year: u16,
+    period: Option<(String, String)>,
     apartment_info: types::ApartmentInfo,
     holidays: BTreeMap<String, String>,
     exclude_sunday: bool,
-) -> String {
-    // TODO: unwrapping is not so nice
-    let holidays: BTreeMap<LocalDate, String> = holidays
-        .iter()
-        .map(|(key, value)| (string_to_local_date(key.as_str()).unwrap(), value.clone()))
-        .collect();
+) -> Result<String, String> {
+    let plan = create_plan(
+        &config,
+        year,
+        period,
+        &apartment_info,
+        holidays,
+        exclude_sunday,
+    )?;
+    html::create_year_html(&config, &plan).map_err(|err| err.to_string())
+}
 
-    let last_apartment =
-        types::Apartment::new(&config, &apartment_info, &config.position_map).unwrap();
-    let year_map =
-        types::create_full_year(&config, year, last_apartment, exclude_sunday, &holidays);
+#[tauri::command]
+fn create_laundry_plan_pdf(
+    app: tauri::AppHandle,
+    config: types::Config,
+    year: u16,
+    period: Option<(String, String)>,
+    apartment_info: types::ApartmentInfo,
+    holidays: BTreeMap<String, String>,
+    exclude_sunday: bool,
+) -> Result<(), String> {
+    let plan = create_plan(
+        &config,
+        year,
+        period,
+        &apartment_info,
+        holidays,
+        exclude_sunday,
+    )?;
+    let content = pdf::create_year_pdf(&config, &plan).map_err(|err| err.to_string())?;
+    app.dialog()
+        .file()
+        .add_filter(".pdf", &["pdf"])
+        .save_file(move |file_path_opt| {
+            if let Some(file_path) = file_path_opt {
+                file_path.into_path().iter().for_each(|path| {
+                    std::fs::write(path, &content).ok();
+                })
+            }
+        });
+    Ok(())
+}
+
+// @param apartment only export the laundry days of this apartment if given
+#[tauri::command]
+fn create_laundry_plan_ics(
+    app: tauri::AppHandle,
+    config: types::Config,
+    year: u16,
+    period: Option<(String, String)>,
+    apartment_info: types::ApartmentInfo,
+    holidays: BTreeMap<String, String>,
+    exclude_sunday: bool,
+    apartment: Option<plan::ApartmentId>,
+) -> Result<(), String> {
+    let plan = create_plan(
+        &config,
+        year,
+        period,
+        &apartment_info,
+        holidays,
+        exclude_sunday,
+    )?;
+    let content = ics::create_calendar(&config, &plan, apartment);
+    app.dialog()
+        .file()
+        .add_filter(".ics", &["ics"])
+        .save_file(move |file_path_opt| {
+            if let Some(file_path) = file_path_opt {
+                file_path.into_path().iter().for_each(|path| {
+                    std::fs::write(path, &content).ok();
+                })
+            }
+        });
+    Ok(())
+}
 
-    html::create_year_html(&config, &year_map, year)
+#[tauri::command]
+fn export_laundry_plan(
+    app: tauri::AppHandle,
+    config: types::Config,
+    year: u16,
+    period: Option<(String, String)>,
+    apartment_info: types::ApartmentInfo,
+    holidays: BTreeMap<String, String>,
+    exclude_sunday: bool,
+    format: export::ExportFormat,
+) -> Result<(), String> {
+    let plan = create_plan(
+        &config,
+        year,
+        period,
+        &apartment_info,
+        holidays,
+        exclude_sunday,
+    )?;
+    let export_days = export::create_export_days(&config, &plan);
+    let content = export::create_export(&export_days, format).map_err(|err| err.to_string())?;
+    let (name, extension) = match format {
+        export::ExportFormat::Csv => (".csv", "csv"),
+        export::ExportFormat::Json => (".json", "json"),
+    };
+    app.dialog()
+        .file()
+        .add_filter(name, &[extension])
+        .save_file(move |file_path_opt| {
+            if let Some(file_path) = file_path_opt {
+                file_path.into_path().iter().for_each(|path| {
+                    std::fs::write(path, &content).ok();
+                })
+            }
+        });
+    Ok(())
+}
+
+fn workspace(app: &tauri::AppHandle) -> Result<workspace::Workspace, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| workspace::Workspace::new(dir.join("workspace")))
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn list_buildings(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+    workspace(&app)?.list().map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn get_building(app: tauri::AppHandle, name: String) -> Result<workspace::Building, String> {
+    workspace(&app)?
+        .read_building(&name)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn create_building(
+    app: tauri::AppHandle,
+    name: String,
+    config: types::Config,
+    building: workspace::Building,
+) -> Result<(), String> {
+    workspace(&app)?
+        .create(&name, config, &building)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn duplicate_building(
+    app: tauri::AppHandle,
+    name: String,
+    new_name: String,
+) -> Result<workspace::Building, String> {
+    workspace(&app)?
+        .duplicate(&name, &new_name)
+        .map_err(|err| err.to_string())
+}
+
+// @return (building, error) for every building whose plan couldn't be generated
+#[tauri::command]
+async fn generate_all_plans(
+    app: tauri::AppHandle,
+    year: u16,
+    period: Option<(String, String)>,
+    cache_max_age: Option<u64>,
+) -> Result<Vec<(String, String)>, String> {
+    let (start, end) = match period {
+        Some((start, end)) => (
+            types::string_to_local_date(&start).ok_or(format!("Invalid date '{}'.", start))?,
+            types::string_to_local_date(&end).ok_or(format!("Invalid date '{}'.", end))?,
+        ),
+        None => types::full_year(year),
+    };
+    let cache = holiday_cache(&app, cache_max_age);
+    let results = workspace(&app)?
+        .generate_all(start, end, cache.as_ref())
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(results
+        .into_iter()
+        .filter_map(|(name, result)| result.err().map(|err| (name, err.to_string())))
+        .collect())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -128,11 +497,23 @@ pub fn run() {
         .plugin(tauri_plugin_http::init())
         .invoke_handler(tauri::generate_handler![
             create_laundry_plan,
+            get_laundry_plan,
+            get_plan_stats,
+            create_laundry_plan_pdf,
+            create_laundry_plan_ics,
+            export_laundry_plan,
             get_subdivisions,
             get_holidays,
+            refresh_holidays,
+            check_holidays,
             print_window,
             save_config,
-            read_config
+            read_config,
+            list_buildings,
+            get_building,
+            create_building,
+            duplicate_building,
+            generate_all_plans
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
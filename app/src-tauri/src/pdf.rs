@@ -0,0 +1,312 @@
+use crate::layout::{Layout, PageSplit};
+use crate::plan::{Plan, PlanDay};
+use crate::types::{self, DayHTMLData};
+use printpdf::{
+    BuiltinFont, Color, IndirectFontRef, Mm, PdfDocument, PdfLayerReference, Rect, Rgb,
+};
+
+// A4, all lengths in mm
+const PAGE_SHORT_SIDE: f32 = 210.0;
+const PAGE_LONG_SIDE: f32 = 297.0;
+const MARGIN: f32 = 10.0;
+const TITLE_HEIGHT: f32 = 10.0;
+const MONTH_GAP: f32 = 4.0;
+// a month header plus the longest month
+const ROWS_PER_MONTH: usize = 32;
+// smallest readable row, four rows of months fit on a portrait page
+const MIN_ROW_HEIGHT: f32 = 2.0;
+// keeps the apartment label inside its column if there are only a few months on a page
+const MAX_ROW_HEIGHT_PER_WIDTH: f32 = 0.175;
+const PT_PER_MM: f32 = 72.0 / 25.4;
+// glyph widths in helvetica relative to the font size
+const HELVETICA_DIGIT_WIDTH: f32 = 0.556;
+const HELVETICA_POINT_WIDTH: f32 = 0.278;
+const HELVETICA_HYPHEN_WIDTH: f32 = 0.333;
+
+struct Fonts {
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+}
+
+struct Page {
+    width: f32,
+    height: f32,
+}
+
+struct Cell {
+    x: f32,
+    y: f32,
+    width: f32,
+    row_height: f32,
+}
+
+fn black() -> Color {
+    Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None))
+}
+
+// same colors as the html preview
+fn sunday_color() -> Color {
+    Color::Rgb(Rgb::new(1.0, 0.0, 0.0, None))
+}
+
+fn holiday_color() -> Color {
+    Color::Rgb(Rgb::new(0.812, 0.957, 0.988, None))
+}
+
+fn blackout_color() -> Color {
+    Color::Rgb(Rgb::new(1.0, 0.953, 0.804, None))
+}
+
+fn stripe_color() -> Color {
+    Color::Rgb(Rgb::new(0.95, 0.95, 0.95, None))
+}
+
+fn font_size(row_height: f32) -> f32 {
+    row_height * PT_PER_MM * 0.75
+}
+
+// @return width in mm of a text made of digits, points, spaces and hyphens
+fn period_width(text: &str, size: f32) -> f32 {
+    let em: f32 = text
+        .chars()
+        .map(|char| match char {
+            '.' | ' ' => HELVETICA_POINT_WIDTH,
+            '-' => HELVETICA_HYPHEN_WIDTH,
+            _ => HELVETICA_DIGIT_WIDTH,
+        })
+        .sum();
+    em * size / PT_PER_MM
+}
+
+fn fill_row(layer: &PdfLayerReference, cell: &Cell, y: f32, color: Color) {
+    layer.set_fill_color(color);
+    layer.add_rect(Rect::new(
+        Mm(cell.x),
+        Mm(y),
+        Mm(cell.x + cell.width),
+        Mm(y + cell.row_height),
+    ));
+}
+
+fn create_row(
+    layer: &PdfLayerReference,
+    fonts: &Fonts,
+    cell: &Cell,
+    index: usize,
+    day_data: &DayHTMLData,
+) {
+    // the header takes the first row
+    let y = cell.y - (index + 2) as f32 * cell.row_height;
+    if day_data.is_holiday {
+        fill_row(layer, cell, y, holiday_color());
+    } else if day_data.is_blackout {
+        fill_row(layer, cell, y, blackout_color());
+    } else if index.is_multiple_of(2) {
+        fill_row(layer, cell, y, stripe_color());
+    }
+
+    let size = font_size(cell.row_height);
+    let text_y = Mm(y + cell.row_height * 0.25);
+    let date_x = cell.x + 1.0;
+    let day_x = cell.x + cell.width * 0.2;
+    let apartment_x = cell.x + cell.width * 0.35;
+
+    layer.set_fill_color(if day_data.is_sunday || day_data.is_excluded {
+        sunday_color()
+    } else {
+        black()
+    });
+    layer.use_text(
+        day_data.date.clone(),
+        size,
+        Mm(date_x),
+        text_y,
+        &fonts.regular,
+    );
+    layer.use_text(
+        day_data.day.clone(),
+        size,
+        Mm(day_x),
+        text_y,
+        &fonts.regular,
+    );
+    layer.set_fill_color(black());
+    // NOTE: a closed day shows its reason once instead of in every slot
+    if day_data.is_closed {
+        layer.use_text(
+            day_data.appartment.clone(),
+            size,
+            Mm(apartment_x),
+            text_y,
+            &fonts.regular,
+        );
+        return;
+    }
+    let slot_width = (cell.x + cell.width - apartment_x) / day_data.slots.len() as f32;
+    day_data
+        .slots
+        .iter()
+        .enumerate()
+        .for_each(|(slot, apartment)| {
+            layer.use_text(
+                apartment.clone(),
+                size,
+                Mm(apartment_x + slot as f32 * slot_width),
+                text_y,
+                &fonts.regular,
+            );
+        });
+}
+
+fn create_month_table(
+    layer: &PdfLayerReference,
+    fonts: &Fonts,
+    cell: &Cell,
+    title: String,
+    day_data: &[DayHTMLData],
+) {
+    layer.set_fill_color(black());
+    layer.use_text(
+        title,
+        font_size(cell.row_height),
+        Mm(cell.x + 1.0),
+        Mm(cell.y - cell.row_height * 0.75),
+        &fonts.bold,
+    );
+    day_data
+        .iter()
+        .enumerate()
+        .for_each(|(index, day_data)| create_row(layer, fonts, cell, index, day_data));
+}
+
+fn create_title(layer: &PdfLayerReference, fonts: &Fonts, page: &Page, title: &str, period: &str) {
+    let size = TITLE_HEIGHT * PT_PER_MM * 0.6;
+    let y = Mm(page.height - MARGIN - TITLE_HEIGHT * 0.6);
+    layer.set_fill_color(black());
+    layer.use_text(title, size, Mm(MARGIN), y, &fonts.bold);
+    layer.use_text(
+        period,
+        size,
+        Mm(page.width - MARGIN - period_width(period, size)),
+        y,
+        &fonts.bold,
+    );
+}
+
+fn create_page(layout: &Layout) -> Page {
+    if layout.is_landscape() {
+        Page {
+            width: PAGE_LONG_SIDE,
+            height: PAGE_SHORT_SIDE,
+        }
+    } else {
+        Page {
+            width: PAGE_SHORT_SIDE,
+            height: PAGE_LONG_SIDE,
+        }
+    }
+}
+
+// @return as many rows as fit on a page, split pages only take the rows they need
+fn month_rows_per_page(layout: &Layout, page: &Page, pages: &[Vec<&[PlanDay]>]) -> usize {
+    let month_height = MIN_ROW_HEIGHT * ROWS_PER_MONTH as f32;
+    let fitting = (((page.height - 2.0 * MARGIN - TITLE_HEIGHT) / month_height) as usize).max(1);
+    if layout.page_split == PageSplit::Continuous {
+        return fitting;
+    }
+    pages
+        .iter()
+        .map(|months| months.len().div_ceil(layout.months_per_row))
+        .max()
+        .unwrap_or(1)
+        .min(fitting)
+}
+
+pub fn create_year_pdf(config: &types::Config, plan: &Plan) -> Result<Vec<u8>, printpdf::Error> {
+    let layout = &config.layout;
+    let page = create_page(layout);
+    let period = plan.print_period();
+    let (document, first_page, first_layer) = PdfDocument::new(
+        format!("{} {}", config.title, period),
+        Mm(page.width),
+        Mm(page.height),
+        "Waschplan",
+    );
+    let fonts = Fonts {
+        regular: document.add_builtin_font(BuiltinFont::Helvetica)?,
+        bold: document.add_builtin_font(BuiltinFont::HelveticaBold)?,
+    };
+
+    let months_per_row = layout.months_per_row;
+    let pages = layout.pages(plan);
+    let month_rows = month_rows_per_page(layout, &page, &pages);
+    let month_width = (page.width - 2.0 * MARGIN - (months_per_row - 1) as f32 * MONTH_GAP)
+        / months_per_row as f32;
+    let month_height = (page.height - 2.0 * MARGIN - TITLE_HEIGHT) / month_rows as f32;
+    let row_height =
+        (month_height / ROWS_PER_MONTH as f32).min(month_width * MAX_ROW_HEIGHT_PER_WIDTH);
+
+    // NOTE: a split page which doesn't fit continues on further pages
+    let sheets: Vec<&[&[PlanDay]]> = pages
+        .iter()
+        .flat_map(|months| months.chunks(months_per_row * month_rows))
+        .collect();
+    sheets
+        .iter()
+        .enumerate()
+        .for_each(|(page_index, page_months)| {
+            let layer = if page_index == 0 {
+                document.get_page(first_page).get_layer(first_layer)
+            } else {
+                let (new_page, layer) =
+                    document.add_page(Mm(page.width), Mm(page.height), "Waschplan");
+                document.get_page(new_page).get_layer(layer)
+            };
+            create_title(&layer, &fonts, &page, &config.title, &period);
+
+            page_months
+                .chunks(months_per_row)
+                .enumerate()
+                .for_each(|(row, row_months)| {
+                    row_months.iter().enumerate().for_each(|(column, days)| {
+                        let cell = Cell {
+                            x: MARGIN + column as f32 * (month_width + MONTH_GAP),
+                            y: page.height - MARGIN - TITLE_HEIGHT - row as f32 * month_height,
+                            width: month_width,
+                            row_height,
+                        };
+                        let data: Vec<DayHTMLData> = days
+                            .iter()
+                            .map(|day| day.create_html_data(config))
+                            .collect();
+                        create_month_table(
+                            &layer,
+                            &fonts,
+                            &cell,
+                            plan.print_month(days, config.locale),
+                            &data,
+                        );
+                    })
+                });
+        });
+
+    document.save_to_bytes()
+}
+
+#[test]
+pub fn split_pdf_pages() {
+    let mut config = types::sample_config();
+    let mut count_pages = |page_split: PageSplit| {
+        config.layout.page_split = page_split;
+        let plan = types::sample_plan(&config, 2025);
+        let content = create_year_pdf(&config, &plan).unwrap();
+        assert!(content.starts_with(b"%PDF"));
+        let content = String::from_utf8_lossy(&content);
+        content.matches("/Type/Page").count() - content.matches("/Type/Pages").count()
+    };
+
+    // four rows of three months fit on a portrait page
+    assert_eq!(count_pages(PageSplit::Continuous), 1);
+    assert_eq!(count_pages(PageSplit::HalfYear), 2);
+    assert_eq!(count_pages(PageSplit::Quarter), 4);
+}
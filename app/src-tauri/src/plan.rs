@@ -0,0 +1,284 @@
+use crate::i18n::Locale;
+use crate::types::{self, ApartmentLabel, DayHTMLData};
+use datetime::{DatePiece, LocalDate};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<datetime::Weekday> for Weekday {
+    fn from(weekday: datetime::Weekday) -> Self {
+        match weekday {
+            datetime::Weekday::Monday => Self::Monday,
+            datetime::Weekday::Tuesday => Self::Tuesday,
+            datetime::Weekday::Wednesday => Self::Wednesday,
+            datetime::Weekday::Thursday => Self::Thursday,
+            datetime::Weekday::Friday => Self::Friday,
+            datetime::Weekday::Saturday => Self::Saturday,
+            datetime::Weekday::Sunday => Self::Sunday,
+        }
+    }
+}
+
+// an apartment identified by its floor and its index on the floor counted from the left
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ApartmentId {
+    pub floor: u32,
+    pub unit: usize,
+}
+
+impl ApartmentId {
+    // @return the label of the apartment as it appears in the plan, e.g. "2. Links"
+    pub fn print(&self, config: &types::Config) -> String {
+        let metadata = config.metadata(*self);
+        let label = metadata.and_then(|metadata| metadata.label.clone().or(metadata.door.clone()));
+        let tenant = metadata
+            .and_then(|metadata| metadata.tenant.clone())
+            .filter(|_| !config.hide_names);
+        let name = match config.apartment_label {
+            ApartmentLabel::Position => None,
+            ApartmentLabel::Label => label,
+            ApartmentLabel::Tenant => tenant.or(label),
+        };
+        name.unwrap_or_else(|| types::print_apartment(config, self.floor, self.unit))
+    }
+}
+
+// why nobody washes on a day
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    // name of the holiday
+    Holiday(String),
+    // name of the blackout of the house
+    Blackout(String),
+    // the laundry room is closed on this weekday
+    ExcludedWeekday(Weekday),
+    // the apartment whose turn it would be
+    Vacancy(ApartmentId),
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum Assignment {
+    Apartment(ApartmentId),
+    Skipped(SkipReason),
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PlanDay {
+    #[serde(serialize_with = "serialize_date")]
+    pub date: LocalDate,
+    pub weekday: Weekday,
+    // one per slot in the order of `Config::slots`
+    pub assignments: Vec<Assignment>,
+    // name of the holiday on this day, if any
+    pub holiday: Option<String>,
+    // name of the blackout on this day, if any
+    pub blackout: Option<String>,
+}
+
+// the laundry schedule from start to end without any formatting
+#[derive(Serialize, Clone, Debug)]
+pub struct Plan {
+    #[serde(serialize_with = "serialize_date")]
+    pub start: LocalDate,
+    #[serde(serialize_with = "serialize_date")]
+    pub end: LocalDate,
+    pub days: Vec<PlanDay>,
+    // washed last at the end of the plan, seeds the plan that follows
+    pub last_apartment: types::ApartmentInfo,
+}
+
+// ISO 8601, e.g. 2025-08-01
+pub fn print_iso_date(date: LocalDate) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.year(),
+        date.month().months_from_january() + 1,
+        date.day()
+    )
+}
+
+pub(crate) fn serialize_date<S: Serializer>(
+    date: &LocalDate,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&print_iso_date(*date))
+}
+
+pub(crate) fn deserialize_date<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<LocalDate, D::Error> {
+    let date = String::deserialize(deserializer)?;
+    LocalDate::from_str(&date)
+        .map_err(|_| serde::de::Error::custom(format!("invalid date '{}'", date)))
+}
+
+impl PlanDay {
+    // @return the month from 1 to 12
+    pub fn month(&self) -> u8 {
+        self.date.month().months_from_january() as u8 + 1
+    }
+
+    // @return None if nobody washes in the slot
+    pub fn apartment(&self, slot: usize) -> Option<ApartmentId> {
+        match self.assignments.get(slot)? {
+            Assignment::Apartment(apartment) => Some(*apartment),
+            Assignment::Skipped(_) => None,
+        }
+    }
+
+    // @return the apartments washing on this day in the order of their slots
+    pub fn apartments(&self) -> Vec<ApartmentId> {
+        (0..self.assignments.len())
+            .filter_map(|slot| self.apartment(slot))
+            .collect()
+    }
+
+    // @return true if the whole day is skipped, a vacancy only frees a single slot
+    pub fn is_closed(&self) -> bool {
+        self.assignments.iter().all(|assignment| {
+            matches!(
+                assignment,
+                Assignment::Skipped(
+                    SkipReason::Holiday(_)
+                        | SkipReason::Blackout(_)
+                        | SkipReason::ExcludedWeekday(_)
+                )
+            )
+        })
+    }
+
+    pub fn create_html_data(&self, config: &types::Config) -> DayHTMLData {
+        let locale = config.locale;
+        let slots: Vec<String> = self
+            .assignments
+            .iter()
+            .map(|assignment| match assignment {
+                Assignment::Apartment(apartment) => apartment.print(config),
+                Assignment::Skipped(SkipReason::Holiday(name)) => name.clone(),
+                Assignment::Skipped(SkipReason::Blackout(name)) => name.clone(),
+                Assignment::Skipped(SkipReason::ExcludedWeekday(_)) => "".to_owned(),
+                Assignment::Skipped(SkipReason::Vacancy(_)) => locale.free().to_owned(),
+            })
+            .collect();
+        DayHTMLData {
+            date: types::print_local_date(self.date),
+            day: locale.weekday(self.weekday).to_owned(),
+            appartment: slots[0].clone(),
+            slots,
+            is_closed: self.is_closed(),
+            is_holiday: self.holiday.is_some(),
+            is_blackout: self.holiday.is_none() && self.blackout.is_some(),
+            is_sunday: self.weekday == Weekday::Sunday,
+            is_excluded: config.excluded_weekdays.contains(&self.weekday),
+        }
+    }
+}
+
+impl Plan {
+    // @return the days of every month in calendar order
+    pub fn months(&self) -> Vec<&[PlanDay]> {
+        self.days
+            .chunk_by(|day, next| day.date.month() == next.date.month())
+            .collect()
+    }
+
+    pub fn is_full_year(&self) -> bool {
+        types::full_year(self.start.year() as u16) == (self.start, self.end)
+    }
+
+    // @return the year for a full year, the first and the last day otherwise
+    pub fn print_period(&self) -> String {
+        if self.is_full_year() {
+            self.start.year().to_string()
+        } else {
+            format!(
+                "{} - {}",
+                types::print_full_local_date(self.start),
+                types::print_full_local_date(self.end)
+            )
+        }
+    }
+
+    // @param days the days of one month as returned by `months`
+    // @return the name of the month and the year if the plan covers more than one year
+    pub fn print_month(&self, days: &[PlanDay], locale: Locale) -> String {
+        let date = days[0].date;
+        let month = locale.month(date.month()).to_owned();
+        if self.start.year() == self.end.year() {
+            month
+        } else {
+            format!("{} {}", month, date.year())
+        }
+    }
+}
+
+#[test]
+pub fn record_skip_reasons() {
+    let mut config = types::sample_config();
+    config.position_map = std::collections::HashMap::from([(
+        0,
+        types::FloorInfo::OneApartment(types::SingleApartmentFloorInfo { days_total: 1 }),
+    )]);
+    config.excluded_weekdays = vec![Weekday::Saturday];
+    let new_year = LocalDate::ymd(2025, datetime::Month::January, 1).unwrap();
+    let holidays = std::collections::BTreeMap::from([(new_year, "Neujahr".to_string())]);
+    let plan =
+        types::create_full_year(&config, 2025, &types::sample_state(), true, &holidays).unwrap();
+
+    assert_eq!(plan.days.len(), 365);
+    assert_eq!(plan.months().len(), 12);
+    assert_eq!(
+        plan.days[0].assignments[0],
+        Assignment::Skipped(SkipReason::Holiday("Neujahr".to_string()))
+    );
+    // 5.1.2025 is a Sunday
+    assert_eq!(plan.days[4].weekday, Weekday::Sunday);
+    assert_eq!(
+        plan.days[4].assignments[0],
+        Assignment::Skipped(SkipReason::ExcludedWeekday(Weekday::Sunday))
+    );
+    assert_eq!(
+        plan.days[1].apartment(0),
+        Some(ApartmentId { floor: 0, unit: 0 })
+    );
+    // 4.1.2025 is a Saturday
+    assert_eq!(
+        plan.days[3].assignments[0],
+        Assignment::Skipped(SkipReason::ExcludedWeekday(Weekday::Saturday))
+    );
+    assert!(plan.days[3].create_html_data(&config).is_excluded);
+}
+
+#[test]
+pub fn hide_tenant_names() {
+    let mut config = types::sample_config();
+    config.apartments = vec![types::ApartmentMetadata {
+        floor: 2,
+        position: types::FloorPosition::Middle,
+        tenant: Some("Muster".to_string()),
+        door: Some("2.2".to_string()),
+        email: None,
+        phone: None,
+        label: None,
+        vacant: false,
+        absences: vec![],
+    }];
+    config.apartment_label = ApartmentLabel::Tenant;
+    let middle = ApartmentId { floor: 2, unit: 1 };
+    let right = ApartmentId { floor: 2, unit: 2 };
+
+    assert_eq!(middle.print(&config), "Muster");
+    assert_eq!(right.print(&config), "2. Rechts");
+    config.hide_names = true;
+    assert_eq!(middle.print(&config), "2.2");
+}
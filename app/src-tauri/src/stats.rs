@@ -0,0 +1,174 @@
+use crate::plan::{ApartmentId, Assignment, Plan, SkipReason, Weekday};
+use crate::types;
+use serde::Serialize;
+
+pub const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+// what an apartment got in a plan, a day with several slots counts once per slot
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ApartmentStats {
+    pub apartment: ApartmentId,
+    // label as printed in the plan, e.g. "2. Links"
+    pub label: String,
+    // consecutive days of a turn as set in the config
+    pub days_total: u8,
+    pub days: usize,
+    // days per weekday from Monday to Sunday
+    pub weekdays: [usize; 7],
+    pub saturdays: usize,
+    // days in the popular months of a fair scheduler
+    pub popular_days: usize,
+    // days right before or after a holiday
+    pub next_to_holidays: usize,
+    // most days between two laundry days, 0 if the apartment washes less than twice
+    pub longest_gap: usize,
+    // slots left free b/c the apartment was vacant or away, see `VacancyPolicy::LeaveFree`
+    pub skipped_days: usize,
+    // days of the plan on which the apartment is vacant or its tenant is away
+    pub absent_days: usize,
+}
+
+// @return the statistics of every apartment ordered by floor and position
+pub fn create_stats(config: &types::Config, plan: &Plan) -> Vec<ApartmentStats> {
+    let popular_months = config.scheduler.popular_months();
+    let is_holiday = |index: Option<usize>| {
+        index
+            .and_then(|index| plan.days.get(index))
+            .is_some_and(|day| day.holiday.is_some())
+    };
+    config
+        .apartment_ids()
+        .iter()
+        .map(|apartment| {
+            // NOTE: the days of a plan follow each other, so the index counts days
+            let indices: Vec<usize> = plan
+                .days
+                .iter()
+                .enumerate()
+                .flat_map(|(index, day)| {
+                    day.apartments()
+                        .into_iter()
+                        .filter(|id| id == apartment)
+                        .map(move |_| index)
+                })
+                .collect();
+            let mut weekdays = [0; 7];
+            indices.iter().for_each(|index| {
+                let weekday = plan.days[*index].weekday;
+                // NOTE: safe b/c every weekday is in the list
+                weekdays[WEEKDAYS.iter().position(|day| *day == weekday).unwrap()] += 1;
+            });
+            ApartmentStats {
+                apartment: *apartment,
+                label: apartment.print(config),
+                days_total: config.days_total(*apartment),
+                days: indices.len(),
+                weekdays,
+                saturdays: weekdays[5],
+                popular_days: indices
+                    .iter()
+                    .filter(|index| popular_months.contains(&plan.days[**index].month()))
+                    .count(),
+                next_to_holidays: indices
+                    .iter()
+                    .filter(|index| {
+                        is_holiday(index.checked_sub(1)) || is_holiday(Some(*index + 1))
+                    })
+                    .count(),
+                longest_gap: indices
+                    .windows(2)
+                    .map(|pair| (pair[1] - pair[0]).saturating_sub(1))
+                    .max()
+                    .unwrap_or(0),
+                skipped_days: plan
+                    .days
+                    .iter()
+                    .flat_map(|day| day.assignments.iter())
+                    .filter(|assignment| {
+                        **assignment == Assignment::Skipped(SkipReason::Vacancy(*apartment))
+                    })
+                    .count(),
+                absent_days: plan
+                    .days
+                    .iter()
+                    .filter(|day| config.is_absent(*apartment, day.date))
+                    .count(),
+            }
+        })
+        .collect()
+}
+
+// @return a plain text table of the statistics, one line per apartment
+pub fn print_report(config: &types::Config, stats: &[ApartmentStats]) -> String {
+    let label_width = stats
+        .iter()
+        .map(|stats| stats.label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let weekdays: Vec<String> = WEEKDAYS
+        .iter()
+        .map(|weekday| format!("{:>4}", config.locale.weekday(*weekday)))
+        .collect();
+    let mut lines = vec![format!(
+        "{:<label_width$} {:>5} {}  {:>8} {:>4} {:>7} {:>6}",
+        "",
+        "days",
+        weekdays.join(""),
+        "holidays",
+        "gap",
+        "skipped",
+        "absent",
+    )];
+    lines.extend(stats.iter().map(|stats| {
+        let weekdays: Vec<String> = stats
+            .weekdays
+            .iter()
+            .map(|days| format!("{:>4}", days))
+            .collect();
+        format!(
+            "{:<label_width$} {:>5} {}  {:>8} {:>4} {:>7} {:>6}",
+            stats.label,
+            stats.days,
+            weekdays.join(""),
+            stats.next_to_holidays,
+            stats.longest_gap,
+            stats.skipped_days,
+            stats.absent_days,
+        )
+    }));
+    lines.push("".to_owned());
+    lines.join("\n")
+}
+
+#[test]
+pub fn count_days_per_apartment() {
+    let config = types::sample_config();
+    let new_year = datetime::LocalDate::ymd(2025, datetime::Month::January, 1).unwrap();
+    let holidays = std::collections::BTreeMap::from([(new_year, "Neujahr".to_string())]);
+    let plan =
+        types::create_full_year(&config, 2025, &types::sample_state(), true, &holidays).unwrap();
+    let stats = create_stats(&config, &plan);
+
+    assert_eq!(stats.len(), 9);
+    // every day but the holiday and the Sundays
+    assert_eq!(
+        stats.iter().map(|stats| stats.days).sum::<usize>(),
+        364 - 52
+    );
+    assert!(stats.iter().all(|stats| stats.weekdays[6] == 0));
+    assert!(stats
+        .iter()
+        .all(|stats| stats.days == stats.weekdays.iter().sum::<usize>()));
+    // the left apartment of the first floor washes the two days after New Year
+    assert_eq!(stats[1].next_to_holidays, 1);
+    // a round takes 15 days, the Sundays only make the gaps longer
+    assert!(stats[0].longest_gap >= 14);
+}
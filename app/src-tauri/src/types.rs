@@ -1,4 +1,8 @@
-use datetime::{DatePiece, LocalDate, Month, Weekday};
+use crate::fairness::{self, Scheduler};
+use crate::i18n::Locale;
+use crate::layout::{self, Layout};
+use crate::plan::{self, Plan, PlanDay, SkipReason};
+use datetime::{DatePiece, LocalDate, Month};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, HashMap},
@@ -10,14 +14,395 @@ use std::{
 pub struct Config {
     pub position_map: HashMap<u32, FloorInfo>,
     pub title: String,
+    // NOTE: configs written before the localization are german
+    #[serde(default)]
+    pub locale: Locale,
+    // path of a template file for the HTML plan, see `html::render_plan`
+    #[serde(default)]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub layout: Layout,
+    #[serde(default)]
+    pub apartments: Vec<ApartmentMetadata>,
+    #[serde(default)]
+    pub apartment_label: ApartmentLabel,
+    // NOTE: for publicly posted plans, hides tenant names and contact details
+    #[serde(default)]
+    pub hide_names: bool,
+    #[serde(default)]
+    pub vacancy_policy: VacancyPolicy,
+    // days on which the laundry room is closed, in addition to an excluded Sunday
+    #[serde(default)]
+    pub excluded_weekdays: Vec<plan::Weekday>,
+    #[serde(default)]
+    pub blackouts: Vec<Blackout>,
+    #[serde(default)]
+    pub holiday_policy: HolidayPolicy,
+    // labels of the slots of a day, e.g. "Vormittag" and "Nachmittag", empty for a single slot
+    #[serde(default)]
+    pub slots: Vec<String>,
+    #[serde(default)]
+    pub scheduler: Scheduler,
+    #[serde(default)]
+    pub rotation_order: RotationOrder,
+    // every floor from the bottom up, e.g. a basement below the ground floor, empty to order the
+    // floors by their numbers
+    // NOTE: the numbers only identify the floors, they don't have to be contiguous
+    #[serde(default)]
+    pub floors: Vec<FloorLabel>,
+}
+
+// what happens to the apartment whose day falls on a public holiday
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HolidayPolicy {
+    // nobody washes and the rotation waits, every following turn moves by one day
+    #[default]
+    Pause,
+    // nobody washes and the apartment loses its day
+    LoseDay,
+    // the apartment washes on the holiday
+    Keep,
+    // the apartment washes on the next free day, the following turns move back by one day
+    Shift,
+}
+
+// the order in which the apartments take turns, a finished round starts over
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RotationOrder {
+    // from the lowest floor up, left to right on every floor
+    #[default]
+    BottomUp,
+    // from the top floor down, left to right on every floor
+    TopDown,
+    // from the lowest floor up, left to right on the lowest floor and then alternating
+    Serpentine,
+    // apartments which aren't listed don't wash
+    Custom(Vec<ApartmentPosition>),
+}
+
+// a floor as it appears in the plan, e.g. "UG" for a basement or "DG" for the attic
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct FloorLabel {
+    pub floor: u32,
+    // the number or "P" for the ground floor if None
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApartmentPosition {
+    pub floor: u32,
+    pub position: FloorPosition,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeekOfMonth {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Last,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub enum BlackoutRule {
+    // e.g. a renovation, a single day starts and ends on the same date
+    Period(Period),
+    // e.g. the first Monday of each month
+    Monthly {
+        week: WeekOfMonth,
+        weekday: plan::Weekday,
+    },
+    // e.g. the house cleaning on 15.4. of every year
+    Yearly {
+        month: u8,
+        day: u8,
+    },
+}
+
+// a day on which the laundry room is closed for the whole house, e.g. for maintenance
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Blackout {
+    // shown in the plan instead of an apartment
+    pub name: String,
+    pub rule: BlackoutRule,
+}
+
+impl BlackoutRule {
+    pub fn matches(&self, date: LocalDate) -> bool {
+        match self {
+            Self::Period(period) => period.contains(date),
+            Self::Monthly { week, weekday } => {
+                let week_index = (date.day() - 1) / 7;
+                let is_week = match week {
+                    WeekOfMonth::First => week_index == 0,
+                    WeekOfMonth::Second => week_index == 1,
+                    WeekOfMonth::Third => week_index == 2,
+                    WeekOfMonth::Fourth => week_index == 3,
+                    // NOTE: yearday 366 (367 in leap years) is in the next year
+                    WeekOfMonth::Last => LocalDate::yd(date.year(), date.yearday() as i64 + 7)
+                        .map(|next_week| next_week.month() != date.month())
+                        .unwrap_or(true),
+                };
+                is_week && plan::Weekday::from(date.weekday()) == *weekday
+            }
+            Self::Yearly { month, day } => {
+                date.month().months_from_january() + 1 == *month as usize
+                    && date.day() == *day as i8
+            }
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            Self::Period(period) => period.start <= period.end,
+            Self::Monthly { .. } => true,
+            // NOTE: 2024 is a leap year, so 29.2. is allowed
+            Self::Yearly { month, day } => Month::from_one(*month as i8)
+                .and_then(|month| LocalDate::ymd(2024, month, *day as i8))
+                .is_ok(),
+        }
+    }
+}
+
+// what happens to the turn of an apartment which is vacant or whose tenant is away
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VacancyPolicy {
+    // the next apartment washes instead
+    #[default]
+    PassOn,
+    // nobody washes on the days of the apartment
+    LeaveFree,
+}
+
+// from start to end, both included, e.g. {"start": "2025-07-01", "end": "2025-08-31"}
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Period {
+    #[serde(
+        serialize_with = "plan::serialize_date",
+        deserialize_with = "plan::deserialize_date"
+    )]
+    pub start: LocalDate,
+    #[serde(
+        serialize_with = "plan::serialize_date",
+        deserialize_with = "plan::deserialize_date"
+    )]
+    pub end: LocalDate,
+}
+
+impl Period {
+    pub fn contains(&self, date: LocalDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+// what the plan shows for an apartment, falls back to the position if the metadata is missing
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ApartmentLabel {
+    // e.g. "2. Links"
+    #[default]
+    Position,
+    // the label or else the door number
+    Label,
+    // the tenant name or else the label
+    Tenant,
+}
+
+// optional details of the apartment at position on floor
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ApartmentMetadata {
+    pub floor: u32,
+    pub position: FloorPosition,
+    pub tenant: Option<String>,
+    pub door: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub label: Option<String>,
+    // empty until further notice
+    #[serde(default)]
+    pub vacant: bool,
+    // e.g. a tenant who is away for months
+    #[serde(default)]
+    pub absences: Vec<Period>,
+}
+
+impl Config {
+    // @return None if there is no apartment at the position of the metadata
+    pub fn apartment_id(&self, metadata: &ApartmentMetadata) -> Option<plan::ApartmentId> {
+        self.position_id(metadata.floor, &metadata.position)
+    }
+
+    pub fn metadata(&self, apartment: plan::ApartmentId) -> Option<&ApartmentMetadata> {
+        self.apartments
+            .iter()
+            .find(|metadata| self.apartment_id(metadata) == Some(apartment))
+    }
+
+    // @return the floors with apartments from the bottom up
+    pub fn floor_order(&self) -> Vec<u32> {
+        if self.floors.is_empty() {
+            let mut floors: Vec<u32> = self.position_map.keys().copied().collect();
+            floors.sort_unstable();
+            return floors;
+        }
+        // NOTE: the validation ensures that every floor with apartments is listed
+        self.floors
+            .iter()
+            .map(|label| label.floor)
+            .filter(|floor| self.position_map.contains_key(floor))
+            .collect()
+    }
+
+    fn custom_floor_label(&self, floor: u32) -> Option<&str> {
+        self.floors
+            .iter()
+            .find(|label| label.floor == floor)
+            .and_then(|label| label.label.as_deref())
+    }
+
+    // @return the label of the floor, the number or "P" for the ground floor if it has none
+    pub fn floor_label(&self, floor: u32) -> String {
+        self.custom_floor_label(floor)
+            .map_or_else(|| print_floor(floor, self.locale), str::to_owned)
+    }
+
+    // @return the apartments of the floor from the left
+    fn floor_apartments(&self, floor: u32) -> Vec<plan::ApartmentId> {
+        let count = self
+            .position_map
+            .get(&floor)
+            .map_or(0, FloorInfo::unit_count);
+        (0..count)
+            .map(|unit| plan::ApartmentId { floor, unit })
+            .collect()
+    }
+
+    // @return every apartment of the house ordered by floor and position
+    pub fn apartment_ids(&self) -> Vec<plan::ApartmentId> {
+        self.floor_order()
+            .iter()
+            .flat_map(|floor| self.floor_apartments(*floor))
+            .collect()
+    }
+
+    // @return None if there is no apartment at the position
+    pub fn position_id(&self, floor: u32, position: &FloorPosition) -> Option<plan::ApartmentId> {
+        let unit = self.position_map.get(&floor)?.unit_index(position)?;
+        Some(plan::ApartmentId { floor, unit })
+    }
+
+    // @return the apartments in the order of the rotation
+    pub fn rotation_sequence(&self) -> Vec<plan::ApartmentId> {
+        let floors = self.floor_order();
+        match &self.rotation_order {
+            RotationOrder::BottomUp => self.apartment_ids(),
+            RotationOrder::TopDown => floors
+                .iter()
+                .rev()
+                .flat_map(|floor| self.floor_apartments(*floor))
+                .collect(),
+            // NOTE: the direction depends on the place of the floor, its number may be anything
+            RotationOrder::Serpentine => floors
+                .iter()
+                .enumerate()
+                .flat_map(|(index, floor)| {
+                    let mut apartments = self.floor_apartments(*floor);
+                    if index % 2 == 1 {
+                        apartments.reverse();
+                    }
+                    apartments
+                })
+                .collect(),
+            RotationOrder::Custom(positions) => positions
+                .iter()
+                .filter_map(|position| self.position_id(position.floor, &position.position))
+                .collect(),
+        }
+    }
+
+    pub fn is_rotation_order_valid(&self) -> bool {
+        let sequence = self.rotation_sequence();
+        !sequence.is_empty()
+            && sequence
+                .iter()
+                .enumerate()
+                .all(|(index, apartment)| !sequence[..index].contains(apartment))
+    }
+
+    // @return the consecutive days of a turn of the apartment
+    pub fn days_total(&self, apartment: plan::ApartmentId) -> u8 {
+        self.position_map
+            .get(&apartment.floor)
+            .map_or(0, |info| info.get_total_days(apartment.unit))
+    }
+
+    // @param days_left days of the current turn after the last day of a plan
+    pub fn apartment_info(&self, apartment: plan::ApartmentId, days_left: u8) -> ApartmentInfo {
+        // NOTE: an unknown floor falls back to the position of a single apartment
+        let position = self
+            .position_map
+            .get(&apartment.floor)
+            .map_or(FloorPosition::Left, |info| {
+                info.floor_position(apartment.unit)
+            });
+        ApartmentInfo {
+            current_floor: apartment.floor,
+            position,
+            days_left,
+            moved_days: vec![],
+        }
+    }
+
+    // @return the number of apartments that wash on a day, at least one
+    pub fn slot_count(&self) -> usize {
+        self.slots.len().max(1)
+    }
+
+    // @return None for the single slot of a plan without slots
+    pub fn slot_label(&self, slot: usize) -> Option<&str> {
+        self.slots.get(slot).map(String::as_str)
+    }
+
+    // @return the name of the first blackout on date
+    pub fn blackout(&self, date: LocalDate) -> Option<&str> {
+        self.blackouts
+            .iter()
+            .find(|blackout| blackout.rule.matches(date))
+            .map(|blackout| blackout.name.as_str())
+    }
+
+    // @return true if the apartment is vacant or its tenant is away on date
+    pub fn is_absent(&self, apartment: plan::ApartmentId, date: LocalDate) -> bool {
+        self.metadata(apartment).is_some_and(|metadata| {
+            metadata.vacant
+                || metadata
+                    .absences
+                    .iter()
+                    .any(|absence| absence.contains(date))
+        })
+    }
 }
 
 #[derive(Debug)]
 pub enum ValidationError {
     FloorInfoError(FloorInfoError),
-    FloorSkipped(u32),
+    // @param floor without any apartment
+    EmptyFloor(u32),
+    // @param floor listed twice in the floors
+    DuplicateFloor(u32),
+    // @param floor with apartments missing from the floors
+    UnlistedFloor(u32),
     FormatError(serde_json::Error),
     ApartmentError(ApartmentInfoError),
+    IoError(std::io::Error),
+    InvalidPeriod(LocalDate, LocalDate),
+    InvalidMonthsPerRow(usize),
+    UnknownApartment(u32, FloorPosition),
+    // @param name of the blackout
+    InvalidBlackout(String),
+    InvalidFairness,
+    // a custom rotation order is empty or lists an apartment twice
+    InvalidRotationOrder,
 }
 
 impl Error for ValidationError {}
@@ -26,35 +411,124 @@ impl fmt::Display for ValidationError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::FloorInfoError(err) => fmt::Display::fmt(err, f),
-            Self::FloorSkipped(floor) => write!(
+            Self::EmptyFloor(floor) => write!(
                 f,
-                "Invalid config! Floor {} was skipped in the config.",
+                "Invalid config! Floor {} has no apartments, leave it out of the position map.",
+                floor
+            ),
+            Self::DuplicateFloor(floor) => write!(
+                f,
+                "Invalid config! Floor {} is listed twice in the floors.",
+                floor
+            ),
+            Self::UnlistedFloor(floor) => write!(
+                f,
+                "Invalid config! Floor {} is missing in the floors, list every floor or none.",
                 floor
             ),
             Self::FormatError(err) => fmt::Display::fmt(err, f),
             Self::ApartmentError(err) => fmt::Display::fmt(err, f),
+            Self::IoError(err) => fmt::Display::fmt(err, f),
+            Self::InvalidPeriod(start, end) => write!(
+                f,
+                "The plan ends on {} before it starts on {}.",
+                print_full_local_date(*end),
+                print_full_local_date(*start)
+            ),
+            Self::InvalidMonthsPerRow(months) => write!(
+                f,
+                "Invalid config! {} months per row don't fit a year, use one of {:?}.",
+                months,
+                layout::VALID_MONTHS_PER_ROW
+            ),
+            Self::UnknownApartment(floor, position) => write!(
+                f,
+                "Invalid config! There is no apartment at floor {}, position {:?}.",
+                floor, position
+            ),
+            Self::InvalidBlackout(name) => write!(
+                f,
+                "Invalid config! The dates of the blackout '{}' don't exist.",
+                name
+            ),
+            Self::InvalidRotationOrder => write!(
+                f,
+                "Invalid config! The rotation order has to list every apartment at most once."
+            ),
+            Self::InvalidFairness => write!(
+                f,
+                "Invalid config! Popular months go from 1 to 12 and weights must not be negative."
+            ),
         }
     }
 }
 
-fn validate_config(cfg: &Config) -> Result<(), ValidationError> {
-    let position_map = &cfg.position_map;
-    let mut i;
-    if position_map.contains_key(&0) {
-        i = 0;
-    } else {
-        i = 1;
+pub(crate) fn validate_config(cfg: &Config) -> Result<(), ValidationError> {
+    for (floor, value) in cfg.position_map.iter() {
+        value.validate(*floor)?;
+    }
+    // NOTE: floors without apartments may be listed, e.g. a commercial ground floor
+    if let Some(label) = cfg.floors.iter().enumerate().find_map(|(index, label)| {
+        cfg.floors[..index]
+            .iter()
+            .any(|other| other.floor == label.floor)
+            .then_some(label)
+    }) {
+        return Err(ValidationError::DuplicateFloor(label.floor));
+    }
+    if let Some(floor) = cfg
+        .position_map
+        .keys()
+        .filter(|floor| !cfg.floors.iter().any(|label| label.floor == **floor))
+        .min()
+        .filter(|_| !cfg.floors.is_empty())
+    {
+        return Err(ValidationError::UnlistedFloor(*floor));
     }
-    for (floor, value) in position_map.iter() {
-        if !position_map.contains_key(&i) {
-            return Err(ValidationError::FloorSkipped(i));
+    if !cfg.layout.is_valid() {
+        return Err(ValidationError::InvalidMonthsPerRow(
+            cfg.layout.months_per_row,
+        ));
+    }
+    for metadata in cfg.apartments.iter() {
+        if cfg.apartment_id(metadata).is_none() {
+            return Err(ValidationError::UnknownApartment(
+                metadata.floor,
+                metadata.position,
+            ));
         }
-        i += 1;
-
-        if let Err(err) = value.validate(*floor) {
-            return Err(ValidationError::FloorInfoError(err));
+        if let Some(absence) = metadata
+            .absences
+            .iter()
+            .find(|absence| absence.end < absence.start)
+        {
+            return Err(ValidationError::InvalidPeriod(absence.start, absence.end));
         }
     }
+    if let Some(blackout) = cfg
+        .blackouts
+        .iter()
+        .find(|blackout| !blackout.rule.is_valid())
+    {
+        return Err(ValidationError::InvalidBlackout(blackout.name.clone()));
+    }
+    if let RotationOrder::Custom(positions) = &cfg.rotation_order {
+        if let Some(position) = positions.iter().find(|position| {
+            cfg.position_id(position.floor, &position.position)
+                .is_none()
+        }) {
+            return Err(ValidationError::UnknownApartment(
+                position.floor,
+                position.position,
+            ));
+        }
+    }
+    if !cfg.is_rotation_order_valid() {
+        return Err(ValidationError::InvalidRotationOrder);
+    }
+    if !cfg.scheduler.is_valid() {
+        return Err(ValidationError::InvalidFairness);
+    }
     Ok(())
 }
 
@@ -62,7 +536,10 @@ pub fn config_from_file<P>(path: P) -> Result<Config, ValidationError>
 where
     P: AsRef<std::path::Path>,
 {
-    let file = std::fs::File::open(path).unwrap();
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) => return Err(ValidationError::IoError(err)),
+    };
     let reader = std::io::BufReader::new(file);
     let config = match serde_json::from_reader(reader) {
         Ok(cfg) => cfg,
@@ -80,23 +557,35 @@ where
     std::fs::write(path, content)
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize)]
+// NOTE: `Unit` addresses an apartment by its index from the left, which also
+// works for floors with more than three apartments
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub enum FloorPosition {
     Left,
     Middle,
     Right,
+    Unit(usize),
 }
 
 impl FloorPosition {
-    fn print(&self) -> String {
+    fn print(&self, locale: Locale) -> String {
+        let [left, middle, right] = locale.positions();
         match self {
-            Self::Left => "Links".to_string(),
-            Self::Middle => "Mitte".to_string(),
-            Self::Right => "Rechts".to_string(),
+            Self::Left => left.to_string(),
+            Self::Middle => middle.to_string(),
+            Self::Right => right.to_string(),
+            Self::Unit(unit) => (unit + 1).to_string(),
         }
     }
 }
 
+const TWO_APARTMENT_POSITIONS: [FloorPosition; 2] = [FloorPosition::Left, FloorPosition::Right];
+const THREE_APARTMENT_POSITIONS: [FloorPosition; 3] = [
+    FloorPosition::Left,
+    FloorPosition::Middle,
+    FloorPosition::Right,
+];
+
 #[derive(Copy, Clone, Debug)]
 struct DayState {
     days_left: u8,
@@ -130,36 +619,6 @@ impl DayState {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-struct CurrentFloorState {
-    floor_position: FloorPosition,
-    day_state: DayState,
-}
-
-impl CurrentFloorState {
-    fn new(pos: FloorPosition, days_total: u8) -> CurrentFloorState {
-        CurrentFloorState {
-            floor_position: pos,
-            day_state: DayState::new(days_total),
-        }
-    }
-
-    fn next(&self) -> CurrentFloorState {
-        CurrentFloorState {
-            floor_position: self.floor_position,
-            day_state: self.day_state.next(),
-        }
-    }
-
-    fn is_max(&self) -> bool {
-        self.day_state.is_max()
-    }
-
-    fn print(&self) -> String {
-        self.floor_position.print()
-    }
-}
-
 #[derive(Debug)]
 pub struct FloorInfoError {
     floor: u32,
@@ -194,9 +653,6 @@ impl SingleApartmentFloorInfo {
         }
         Ok(())
     }
-    fn get_total_days(&self, _floor_position: &FloorPosition) -> u8 {
-        self.days_total
-    }
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -221,14 +677,6 @@ impl TwoApartmentFloorInfo {
         }
         Ok(())
     }
-    fn get_total_days(&self, floor_position: &FloorPosition) -> u8 {
-        match floor_position {
-            FloorPosition::Left => self.left_days_total,
-            FloorPosition::Right => self.right_days_total,
-            // should not happen
-            FloorPosition::Middle => 0,
-        }
-    }
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -260,14 +708,12 @@ impl ThreeApartmentFloorInfo {
         }
         Ok(())
     }
+}
 
-    fn get_total_days(&self, floor_position: &FloorPosition) -> u8 {
-        match floor_position {
-            FloorPosition::Left => self.left_days_total,
-            FloorPosition::Middle => self.middle_days_total,
-            FloorPosition::Right => self.right_days_total,
-        }
-    }
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ApartmentUnitInfo {
+    pub name: String,
+    pub days_total: u8,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -275,315 +721,150 @@ pub enum FloorInfo {
     OneApartment(SingleApartmentFloorInfo),
     TwoApartments(TwoApartmentFloorInfo),
     ThreeApartments(ThreeApartmentFloorInfo),
+    // ordered from left to right
+    Apartments(Vec<ApartmentUnitInfo>),
 }
 
 impl FloorInfo {
-    fn validate(&self, floor: u32) -> Result<(), FloorInfoError> {
+    fn validate(&self, floor: u32) -> Result<(), ValidationError> {
         match self {
             FloorInfo::OneApartment(ap) => ap.validate(floor),
             FloorInfo::TwoApartments(ap) => ap.validate(floor),
             FloorInfo::ThreeApartments(ap) => ap.validate(floor),
-        }
-    }
-    // TODO: find a cleaner way than supplying a position for one apartment homes
-    fn get_total_days(&self, floor_position: &FloorPosition) -> u8 {
-        match self {
-            FloorInfo::OneApartment(ap) => ap.get_total_days(floor_position),
-            FloorInfo::TwoApartments(ap) => ap.get_total_days(floor_position),
-            FloorInfo::ThreeApartments(ap) => ap.get_total_days(floor_position),
-        }
-    }
-}
-
-fn initial_appartment_position(floor_info: &FloorInfo) -> Position {
-    match floor_info {
-        FloorInfo::OneApartment(info) => Position::SingleApartmentFloor(
-            SingleApartmentFloorPos::new(info.days_total, info.days_total - 1),
-        ),
-        FloorInfo::TwoApartments(info) => Position::TwoApartmentFloor(
-            TwoApartmentFloorPos::new_left(info.left_days_total, info.left_days_total - 1),
-        ),
-        FloorInfo::ThreeApartments(info) => Position::ThreeApartmentFloor(
-            ThreeApartmentFloorPos::new_left(info.left_days_total, info.left_days_total - 1),
-        ),
-    }
-}
-
-fn create_position(
-    floor_info: &FloorInfo,
-    floor_position: &FloorPosition,
-    floor: u32,
-    days_left: u8,
-) -> Result<Position, ValidationError> {
-    match floor_info {
-        FloorInfo::OneApartment(info) => {
-            let position = SingleApartmentFloorPos::new(info.days_total, days_left);
-            Ok(Position::SingleApartmentFloor(position))
-        }
-        FloorInfo::TwoApartments(info) => match floor_position {
-            FloorPosition::Left => {
-                let position = TwoApartmentFloorPos::new_left(info.left_days_total, days_left);
-                Ok(Position::TwoApartmentFloor(position))
-            }
-            FloorPosition::Right => {
-                let position = TwoApartmentFloorPos::new_right(info.right_days_total, days_left);
-                Ok(Position::TwoApartmentFloor(position))
-            }
-            FloorPosition::Middle => Err(ValidationError::ApartmentError(
-                ApartmentInfoError::PositionFloorMismatch(*floor_position, floor),
-            )),
-        },
-        FloorInfo::ThreeApartments(info) => match floor_position {
-            FloorPosition::Left => {
-                let position = ThreeApartmentFloorPos::new_left(info.left_days_total, days_left);
-                Ok(Position::ThreeApartmentFloor(position))
-            }
-            FloorPosition::Middle => {
-                let position =
-                    ThreeApartmentFloorPos::new_middle(info.middle_days_total, days_left);
-                Ok(Position::ThreeApartmentFloor(position))
-            }
-            FloorPosition::Right => {
-                let position = ThreeApartmentFloorPos::new_right(info.right_days_total, days_left);
-                Ok(Position::ThreeApartmentFloor(position))
+            FloorInfo::Apartments(units) if units.is_empty() => {
+                return Err(ValidationError::EmptyFloor(floor));
             }
-        },
-    }
-}
-
-#[derive(Copy, Clone, Debug)]
-pub struct SingleApartmentFloorPos {
-    position: DayState,
-}
-
-impl SingleApartmentFloorPos {
-    fn new(days_total: u8, days_left: u8) -> SingleApartmentFloorPos {
-        SingleApartmentFloorPos {
-            position: DayState {
-                days_left,
-                days_total,
+            FloorInfo::Apartments(units) => match units.iter().position(|u| u.days_total == 0) {
+                Some(unit) => Err(FloorInfoError {
+                    floor,
+                    position: Some(FloorPosition::Unit(unit)),
+                }),
+                None => Ok(()),
             },
         }
+        .map_err(ValidationError::FloorInfoError)
     }
 
-    fn next(&self) -> SingleApartmentFloorPos {
-        SingleApartmentFloorPos {
-            position: self.position.next(),
+    // @return days per turn of every apartment from left to right
+    fn days_totals(&self) -> Vec<u8> {
+        match self {
+            FloorInfo::OneApartment(info) => vec![info.days_total],
+            FloorInfo::TwoApartments(info) => vec![info.left_days_total, info.right_days_total],
+            FloorInfo::ThreeApartments(info) => vec![
+                info.left_days_total,
+                info.middle_days_total,
+                info.right_days_total,
+            ],
+            FloorInfo::Apartments(units) => units.iter().map(|unit| unit.days_total).collect(),
         }
     }
 
-    fn is_max(&self) -> bool {
-        self.position.is_max()
+    pub fn unit_count(&self) -> usize {
+        match self {
+            FloorInfo::OneApartment(_) => 1,
+            FloorInfo::TwoApartments(_) => 2,
+            FloorInfo::ThreeApartments(_) => 3,
+            FloorInfo::Apartments(units) => units.len(),
+        }
     }
-}
 
-#[derive(Copy, Clone, Debug)]
-pub struct TwoApartmentFloorPos {
-    position: CurrentFloorState,
-}
-
-impl TwoApartmentFloorPos {
-    pub fn new_left(days_total: u8, days_left: u8) -> TwoApartmentFloorPos {
-        let position = CurrentFloorState {
-            floor_position: FloorPosition::Left,
-            day_state: DayState {
-                days_left,
-                days_total: days_total - 1,
-            },
-        };
-        TwoApartmentFloorPos { position }
+    fn get_total_days(&self, unit: usize) -> u8 {
+        self.days_totals().get(unit).copied().unwrap_or(0)
     }
-    pub fn new_right(days_total: u8, days_left: u8) -> TwoApartmentFloorPos {
-        let position = CurrentFloorState {
-            floor_position: FloorPosition::Right,
-            day_state: DayState {
-                days_left,
-                days_total: days_total - 1,
-            },
-        };
-        TwoApartmentFloorPos { position }
-    }
-    fn is_max(&self) -> bool {
-        let position = self.position;
-        if FloorPosition::Right == position.floor_position && position.is_max() {
-            return true;
+
+    fn unit_name(&self, unit: usize, locale: Locale) -> String {
+        match self {
+            FloorInfo::OneApartment(_) => "".to_string(),
+            FloorInfo::TwoApartments(_) => TWO_APARTMENT_POSITIONS
+                .get(unit)
+                .map(|position| position.print(locale))
+                .unwrap_or_default(),
+            FloorInfo::ThreeApartments(_) => THREE_APARTMENT_POSITIONS
+                .get(unit)
+                .map(|position| position.print(locale))
+                .unwrap_or_default(),
+            FloorInfo::Apartments(units) => units
+                .get(unit)
+                .map(|unit| unit.name.clone())
+                .unwrap_or_default(),
         }
-        false
     }
 
-    fn next(&self, floor_info: &TwoApartmentFloorInfo) -> TwoApartmentFloorPos {
-        let position = self.position;
-        if position.is_max() {
-            let (next_floor_position, days_total) =
-                if position.floor_position == FloorPosition::Left {
-                    (FloorPosition::Right, floor_info.right_days_total - 1)
-                } else {
-                    (FloorPosition::Left, floor_info.left_days_total - 1)
-                };
-            let next_position = CurrentFloorState::new(next_floor_position, days_total);
-            return TwoApartmentFloorPos {
-                position: next_position,
-            };
-        }
-        let next_position = self.position.next();
-        TwoApartmentFloorPos {
-            position: next_position,
+    // inverse of `unit_index`, legacy floors keep their named positions
+    fn floor_position(&self, unit: usize) -> FloorPosition {
+        match self {
+            FloorInfo::OneApartment(_) => FloorPosition::Left,
+            FloorInfo::TwoApartments(_) => TWO_APARTMENT_POSITIONS
+                .get(unit)
+                .copied()
+                .unwrap_or(FloorPosition::Unit(unit)),
+            FloorInfo::ThreeApartments(_) => THREE_APARTMENT_POSITIONS
+                .get(unit)
+                .copied()
+                .unwrap_or(FloorPosition::Unit(unit)),
+            FloorInfo::Apartments(_) => FloorPosition::Unit(unit),
         }
     }
 
-    fn print(&self) -> String {
-        self.position.print()
+    // @return index of the apartment from the left if the position exists on this floor
+    fn unit_index(&self, floor_position: &FloorPosition) -> Option<usize> {
+        let count = self.unit_count();
+        match (self, floor_position) {
+            // the position of a single apartment doesn't matter
+            (FloorInfo::OneApartment(_), FloorPosition::Unit(unit)) => (*unit == 0).then_some(0),
+            (FloorInfo::OneApartment(_), _) => Some(0),
+            (_, FloorPosition::Left) => Some(0),
+            (_, FloorPosition::Middle) => (count == 3).then_some(1),
+            (_, FloorPosition::Right) => count.checked_sub(1),
+            (_, FloorPosition::Unit(unit)) => (*unit < count).then_some(*unit),
+        }
     }
 }
 
 #[derive(Copy, Clone, Debug)]
-pub struct ThreeApartmentFloorPos {
-    position: CurrentFloorState,
+pub struct Position {
+    unit: usize,
+    day_state: DayState,
 }
 
-impl ThreeApartmentFloorPos {
-    pub fn new_left(days_total: u8, days_left: u8) -> ThreeApartmentFloorPos {
-        let position = CurrentFloorState {
-            floor_position: FloorPosition::Left,
-            day_state: DayState {
-                days_left,
-                days_total: days_total - 1,
-            },
-        };
-        ThreeApartmentFloorPos { position }
-    }
-    pub fn new_middle(days_total: u8, days_left: u8) -> ThreeApartmentFloorPos {
-        let position = CurrentFloorState {
-            floor_position: FloorPosition::Middle,
-            day_state: DayState {
-                days_left,
-                days_total: days_total - 1,
-            },
-        };
-        ThreeApartmentFloorPos { position }
-    }
-    pub fn new_right(days_total: u8, days_left: u8) -> ThreeApartmentFloorPos {
-        let position = CurrentFloorState {
-            floor_position: FloorPosition::Right,
+impl Position {
+    fn new(floor_info: &FloorInfo, unit: usize, days_left: u8) -> Position {
+        Position {
+            unit,
             day_state: DayState {
                 days_left,
-                days_total: days_total - 1,
+                days_total: floor_info.get_total_days(unit).saturating_sub(1),
             },
-        };
-        ThreeApartmentFloorPos { position }
-    }
-    fn is_max(&self) -> bool {
-        let position = self.position;
-        if FloorPosition::Right == position.floor_position && position.is_max() {
-            return true;
         }
-        false
     }
 
-    fn next(&self, floor_info: &ThreeApartmentFloorInfo) -> ThreeApartmentFloorPos {
-        let position = self.position;
-        if position.is_max() {
-            let (next_floor_position, days_total) = match position.floor_position {
-                FloorPosition::Left => (FloorPosition::Middle, floor_info.middle_days_total - 1),
-                FloorPosition::Middle => (FloorPosition::Right, floor_info.right_days_total - 1),
-                FloorPosition::Right => (FloorPosition::Left, floor_info.left_days_total - 1),
-            };
-            let next_position = CurrentFloorState::new(next_floor_position, days_total);
-            return ThreeApartmentFloorPos {
-                position: next_position,
-            };
-        }
-        let next_position = self.position.next();
-        ThreeApartmentFloorPos {
-            position: next_position,
-        }
-    }
-
-    fn print(&self) -> String {
-        self.position.print()
-    }
-}
-
-#[derive(Copy, Clone, Debug)]
-pub enum Position {
-    SingleApartmentFloor(SingleApartmentFloorPos),
-    TwoApartmentFloor(TwoApartmentFloorPos),
-    ThreeApartmentFloor(ThreeApartmentFloorPos),
-}
-
-impl Position {
-    fn next(&self, floor_info: &FloorInfo) -> Option<Position> {
-        match self {
-            Position::SingleApartmentFloor(pos) => Some(Position::SingleApartmentFloor(pos.next())),
-            Position::TwoApartmentFloor(pos) => {
-                if let FloorInfo::TwoApartments(info) = floor_info {
-                    return Some(Position::TwoApartmentFloor(pos.next(info)));
-                }
-                None
-            }
-            Position::ThreeApartmentFloor(pos) => {
-                if let FloorInfo::ThreeApartments(info) = floor_info {
-                    return Some(Position::ThreeApartmentFloor(pos.next(info)));
-                }
-                None
-            }
-        }
-    }
-    fn is_max(&self) -> bool {
-        match self {
-            Position::SingleApartmentFloor(pos) => pos.is_max(),
-            Position::TwoApartmentFloor(pos) => pos.is_max(),
-            Position::ThreeApartmentFloor(pos) => pos.is_max(),
+    // @return position of the first day of the apartment
+    fn initial(floor_info: &FloorInfo, unit: usize) -> Position {
+        Position {
+            unit,
+            day_state: DayState::new(floor_info.get_total_days(unit).saturating_sub(1)),
         }
     }
 
-    fn print(&self) -> String {
-        match self {
-            Position::SingleApartmentFloor(_) => "".to_string(),
-            Position::TwoApartmentFloor(pos) => pos.print(),
-            Position::ThreeApartmentFloor(pos) => pos.print(),
+    // NOTE: stays on the apartment, the rotation order decides who follows a finished turn
+    fn next(&self) -> Position {
+        Position {
+            unit: self.unit,
+            day_state: self.day_state.next(),
         }
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-struct Floor {
-    max: u32,
-    has_ground_floor: bool,
-    floor: u32,
-}
-
-impl Floor {
-    pub fn next(&self) -> Floor {
-        if self.is_max() {
-            let min = if self.has_ground_floor { 0 } else { 1 };
-            Floor {
-                max: self.max,
-                has_ground_floor: self.has_ground_floor,
-                floor: min,
-            }
-        } else {
-            Floor {
-                max: self.max,
-                has_ground_floor: self.has_ground_floor,
-                floor: self.floor + 1,
-            }
-        }
-    }
-    pub fn print(&self) -> String {
-        if self.floor == 0 {
-            return "P".to_string();
-        }
-        self.floor.to_string()
-    }
-    fn is_max(&self) -> bool {
-        self.floor == self.max
+fn print_floor(floor: u32, locale: Locale) -> String {
+    if floor == 0 {
+        return locale.ground_floor().to_string();
     }
+    floor.to_string()
 }
 
 #[derive(Clone, Debug)]
 pub struct Apartment {
-    floor: Floor,
+    floor: u32,
     position: Position,
 }
 
@@ -592,7 +873,10 @@ pub enum ApartmentInfoError {
     TooHighCurrentFloor(u32, u32),
     TooManyDaysLeft(u8, u8),
     PositionFloorMismatch(FloorPosition, u32),
-    MissingFloor,
+    // @param floor without apartments
+    MissingFloor(u32),
+    // @param apartment with a moved day which isn't part of the building
+    UnknownMovedDay(plan::ApartmentId),
 }
 
 impl Error for ApartmentInfoError {}
@@ -615,16 +899,24 @@ impl fmt::Display for ApartmentInfoError {
                 "Position {:?} doesn't exist on floor {}.",
                 position, floor
             ),
-            Self::MissingFloor => write!(f, "The impossible happened!"),
+            Self::MissingFloor(floor) => write!(f, "There are no apartments on floor {}.", floor),
+            Self::UnknownMovedDay(apartment) => write!(
+                f,
+                "There is no apartment {} on floor {} to make up a moved day.",
+                apartment.unit, apartment.floor
+            ),
         }
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 pub struct ApartmentInfo {
     pub current_floor: u32,
     pub position: FloorPosition,
     pub days_left: u8,
+    // apartments whose days were moved by a holiday and who still wait for them, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub moved_days: Vec<plan::ApartmentId>,
 }
 
 impl ApartmentInfo {
@@ -638,9 +930,25 @@ impl ApartmentInfo {
                 ApartmentInfoError::TooHighCurrentFloor(self.current_floor, max_floor),
             ));
         }
+        let apartments = config.apartment_ids();
+        if let Some(unknown) = self
+            .moved_days
+            .iter()
+            .find(|apartment| !apartments.contains(apartment))
+        {
+            return Err(ValidationError::ApartmentError(
+                ApartmentInfoError::UnknownMovedDay(*unknown),
+            ));
+        }
         match config.position_map.get(&self.current_floor) {
             Some(floor_info) => {
-                let total_days = floor_info.get_total_days(&self.position);
+                let unit = floor_info.unit_index(&self.position).ok_or(
+                    ValidationError::ApartmentError(ApartmentInfoError::PositionFloorMismatch(
+                        self.position,
+                        self.current_floor,
+                    )),
+                )?;
+                let total_days = floor_info.get_total_days(unit);
                 if total_days < self.days_left {
                     return Err(ValidationError::ApartmentError(
                         ApartmentInfoError::TooManyDaysLeft(self.days_left, total_days),
@@ -649,7 +957,7 @@ impl ApartmentInfo {
                 Ok(())
             }
             None => Err(ValidationError::ApartmentError(
-                ApartmentInfoError::MissingFloor,
+                ApartmentInfoError::MissingFloor(self.current_floor),
             )),
         }
     }
@@ -662,54 +970,91 @@ impl Apartment {
         position_map: &HashMap<u32, FloorInfo>,
     ) -> Result<Apartment, ValidationError> {
         apartment_info.validate(config)?;
-        let max_floor = (config.position_map.len() - 1) as u32;
-        let floor = Floor {
-            floor: apartment_info.current_floor,
-            max: max_floor,
-            has_ground_floor: position_map.contains_key(&0),
-        };
+        let floor = apartment_info.current_floor;
         // NOTE: safe b/c the config have been validated
-        let info = position_map.get(&floor.floor).unwrap();
-        let position = create_position(
-            info,
-            &apartment_info.position,
-            apartment_info.current_floor,
-            apartment_info.days_left,
-        )?;
+        let info = position_map.get(&floor).unwrap();
+        let unit =
+            info.unit_index(&apartment_info.position)
+                .ok_or(ValidationError::ApartmentError(
+                    ApartmentInfoError::PositionFloorMismatch(
+                        apartment_info.position,
+                        apartment_info.current_floor,
+                    ),
+                ))?;
+        let position = Position::new(info, unit, apartment_info.days_left);
         Ok(Apartment { floor, position })
     }
 
-    pub fn next(&self, position_map: &HashMap<u32, FloorInfo>) -> Apartment {
-        if self.position.is_max() {
-            let next_floor = self.floor.next();
-            // NOTE: The number of floors always matches the data in the position_map.
-            // This is checked in the config validation.
-            let floor_info = position_map.get(&next_floor.floor).unwrap();
-            Apartment {
-                floor: next_floor,
-                position: initial_appartment_position(floor_info),
-            }
-        } else {
-            let floor_info = position_map.get(&self.floor.floor).unwrap();
-            Apartment {
+    pub fn next(&self, config: &Config) -> Apartment {
+        let sequence = config.rotation_sequence();
+        let index = sequence
+            .iter()
+            .position(|apartment| *apartment == self.id());
+        if index.is_some() && !self.position.day_state.is_max() {
+            return Apartment {
                 floor: self.floor,
-                position: self.position.next(floor_info).unwrap(),
-            }
+                position: self.position.next(),
+            };
+        }
+        // NOTE: an apartment missing from a custom order hands over to the first one at once
+        let next = index.map_or(0, |index| (index + 1) % sequence.len());
+        let next = sequence[next];
+        // NOTE: the rotation sequence only contains apartments of the position_map
+        let floor_info = config.position_map.get(&next.floor).unwrap();
+        Apartment {
+            floor: next.floor,
+            position: Position::initial(floor_info, next.unit),
         }
     }
 
-    pub fn print(&self) -> String {
-        match self.position {
-            Position::SingleApartmentFloor(_) => self.floor.print(),
-            _ => format!("{}. {}", self.floor.print(), self.position.print()),
+    pub fn id(&self) -> plan::ApartmentId {
+        plan::ApartmentId {
+            floor: self.floor,
+            unit: self.position.unit,
         }
     }
+
+    // inverse of `Apartment::new`
+    pub fn info(&self, position_map: &HashMap<u32, FloorInfo>) -> ApartmentInfo {
+        // NOTE: safe b/c the floor has been checked when the apartment was created
+        let floor_info = position_map.get(&self.floor).unwrap();
+        ApartmentInfo {
+            current_floor: self.floor,
+            position: floor_info.floor_position(self.position.unit),
+            days_left: self.position.day_state.days_left,
+            moved_days: vec![],
+        }
+    }
+
+    pub fn print(&self, config: &Config) -> String {
+        print_apartment(config, self.floor, self.position.unit)
+    }
+}
+
+// @return the label of the apartment as it appears in the plan, e.g. "2. Links"
+// NOTE: a floor with a label is printed without the point of a floor number, e.g. "DG Links"
+pub fn print_apartment(config: &Config, floor: u32, unit: usize) -> String {
+    let unit_name = config
+        .position_map
+        .get(&floor)
+        .map(|info| info.unit_name(unit, config.locale))
+        .unwrap_or_default();
+    let floor_name = config.floor_label(floor);
+    if unit_name.is_empty() {
+        floor_name
+    } else if config.custom_floor_label(floor).is_some() {
+        format!("{} {}", floor_name, unit_name)
+    } else {
+        format!("{}. {}", floor_name, unit_name)
+    }
 }
 
 #[derive(Debug)]
 enum ApartmentOfDay {
     CurrentApartment(Apartment),
-    LastApartment(Apartment, String),
+    LastApartment(Apartment, SkipReason),
+    // the rotation continues with the first apartment, the second washes a day moved by a holiday
+    MovedDay(Apartment, plan::ApartmentId),
 }
 
 impl ApartmentOfDay {
@@ -717,167 +1062,263 @@ impl ApartmentOfDay {
         match self {
             Self::CurrentApartment(app) => app,
             Self::LastApartment(app, _) => app,
+            Self::MovedDay(app, _) => app,
         }
     }
 
-    fn print_appartment(&self) -> String {
+    fn assignment(&self) -> plan::Assignment {
         match self {
-            Self::CurrentApartment(app) => app.print(),
-            Self::LastApartment(_, reason) => reason.to_owned(),
+            Self::CurrentApartment(app) => plan::Assignment::Apartment(app.id()),
+            Self::LastApartment(_, reason) => plan::Assignment::Skipped(reason.clone()),
+            Self::MovedDay(_, id) => plan::Assignment::Apartment(*id),
         }
     }
 }
 
 // container type for the data of a day
-#[derive(Debug)]
+#[derive(Serialize, Debug)]
 pub struct DayHTMLData {
     pub date: String,
     pub day: String,
+    // the first slot, kept for templates written before slots were supported
     pub appartment: String,
+    // one entry per slot
+    pub slots: Vec<String>,
+    // nobody washes in any slot b/c of a holiday, a blackout or an excluded weekday
+    pub is_closed: bool,
     pub is_holiday: bool,
+    // a blackout of the house, not a public holiday
+    pub is_blackout: bool,
+    pub is_sunday: bool,
+    // styled like a Sunday
+    pub is_excluded: bool,
 }
 
 #[derive(Debug)]
-pub struct Day {
+struct Day {
     date: LocalDate,
-    appartment: ApartmentOfDay,
-    is_holiday: bool,
-}
-
-#[derive(Debug)]
-struct WrongYearError;
-
-impl Error for WrongYearError {}
-
-impl fmt::Display for WrongYearError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Error: The year changed.")
-    }
+    // one per slot, the last one carries the rotation to the next day
+    appartments: Vec<ApartmentOfDay>,
+    holiday: Option<String>,
+    blackout: Option<String>,
+    // apartments whose days were moved by a holiday and who still wait for them
+    moved: Vec<plan::ApartmentId>,
 }
 
 impl Day {
-    // @return first day of the year
     fn new(
-        year: u16,
+        date: LocalDate,
         appartment: Apartment,
-        position_map: &HashMap<u32, FloorInfo>,
+        config: &Config,
         exclude_sunday: bool,
         holidays: &BTreeMap<LocalDate, String>,
+        mut moved: Vec<plan::ApartmentId>,
     ) -> Day {
-        // This is safe b/c there is always at least one day per year
-
-        let date = LocalDate::yd(year as i64, 1).unwrap();
-        let appartment =
-            create_appartment_of_day(date, appartment, position_map, exclude_sunday, holidays);
-        let is_holiday = holidays.get(&date).is_some();
+        let mut appartments: Vec<ApartmentOfDay> = vec![];
+        // the rotation fills the slots in order
+        for _ in 0..config.slot_count() {
+            let last_appartment = appartments
+                .last()
+                .map_or(appartment.clone(), |last| last.extract_appartment().clone());
+            appartments.push(create_appartment_of_day(
+                date,
+                last_appartment,
+                config,
+                exclude_sunday,
+                holidays,
+                &mut moved,
+            ));
+        }
+        let holiday = holidays.get(&date).cloned();
+        let blackout = config.blackout(date).map(str::to_owned);
         Day {
             date,
-            appartment,
-            is_holiday,
+            appartments,
+            holiday,
+            blackout,
+            moved,
         }
     }
 
-    pub fn next(
+    fn next(
         &self,
-        position_map: &HashMap<u32, FloorInfo>,
+        config: &Config,
         exclude_sunday: bool,
         holidays: &BTreeMap<LocalDate, String>,
-    ) -> Result<Day, Box<dyn Error>> {
-        let current_year = self.date.year();
-        let date = LocalDate::yd(current_year, self.date.yearday() as i64 + 1)?;
+    ) -> Day {
+        Day::new(
+            next_date(self.date),
+            self.last_appartment().clone(),
+            config,
+            exclude_sunday,
+            holidays,
+            self.moved.clone(),
+        )
+    }
 
-        if date.year() != current_year {
-            let err = Box::new(WrongYearError);
-            return Result::Err(err);
-        }
+    fn last_appartment(&self) -> &Apartment {
+        // NOTE: safe b/c there is at least one slot per day
+        self.appartments.last().unwrap().extract_appartment()
+    }
 
-        let app = self.appartment.extract_appartment().clone();
-        let appartment =
-            create_appartment_of_day(date, app, position_map, exclude_sunday, holidays);
-        let is_holiday = holidays.get(&date).is_some();
+    fn create_plan_day(&self) -> PlanDay {
+        PlanDay {
+            date: self.date,
+            weekday: self.date.weekday().into(),
+            assignments: self
+                .appartments
+                .iter()
+                .map(ApartmentOfDay::assignment)
+                .collect(),
+            holiday: self.holiday.clone(),
+            blackout: self.blackout.clone(),
+        }
+    }
+}
 
-        Ok(Day {
-            date,
-            appartment,
-            is_holiday,
+// @return the days of all turns of a round
+// NOTE: stepping through every day of every apartment once visits each apartment
+fn days_per_round(position_map: &HashMap<u32, FloorInfo>) -> usize {
+    position_map
+        .values()
+        .map(|info| {
+            info.days_totals()
+                .iter()
+                .map(|days| *days as usize)
+                .sum::<usize>()
         })
-    }
+        .sum()
+}
 
-    pub fn print(&self) -> String {
-        [
-            print_local_date(self.date),
-            print_local_weekday(self.date),
-            self.appartment.print_appartment(),
-        ]
-        .join(" ")
+// @return the rotation sequence starting after the given apartment, which comes last
+// NOTE: an apartment missing from a custom order isn't part of it, the sequence starts over
+pub(crate) fn rotation_order(config: &Config, apartment: &Apartment) -> Vec<plan::ApartmentId> {
+    let mut order = config.rotation_sequence();
+    if let Some(index) = order.iter().position(|id| *id == apartment.id()) {
+        order.rotate_left(index + 1);
     }
+    order
+}
 
-    pub fn create_html_data(&self) -> DayHTMLData {
-        DayHTMLData {
-            date: print_local_date(self.date),
-            day: print_local_weekday(self.date),
-            appartment: self.appartment.print_appartment(),
-            is_holiday: self.is_holiday,
+// @return the next apartment which is present on date, None if every apartment is absent
+fn next_present_apartment(
+    date: LocalDate,
+    apartment: &Apartment,
+    config: &Config,
+) -> Option<Apartment> {
+    let mut next = apartment.next(config);
+    for _ in 0..days_per_round(&config.position_map) {
+        if !config.is_absent(next.id(), date) {
+            return Some(next);
         }
+        next = next.next(config);
     }
+    None
 }
 
 fn create_appartment_of_day(
     date: LocalDate,
     last_appartment: Apartment,
-    position_map: &HashMap<u32, FloorInfo>,
+    config: &Config,
     exclude_sunday: bool,
     holidays: &BTreeMap<LocalDate, String>,
+    moved: &mut Vec<plan::ApartmentId>,
 ) -> ApartmentOfDay {
-    match holidays.get(&date) {
-        Some(holiday_name) =>
-        // keep a stale value if the current day is not used
-        {
-            ApartmentOfDay::LastApartment(last_appartment, holiday_name.to_owned())
-        }
-        None => {
-            if exclude_sunday && date.weekday() == Weekday::Sunday {
-                // keep a stale value if the current day is not used
-                ApartmentOfDay::LastApartment(last_appartment, "".to_owned())
-            } else {
-                // go to the next appartment
-                ApartmentOfDay::CurrentApartment(last_appartment.next(position_map))
+    if let Some(holiday_name) = holidays.get(&date) {
+        let holiday = SkipReason::Holiday(holiday_name.to_owned());
+        match config.holiday_policy {
+            // keep a stale value if the current day is not used
+            HolidayPolicy::Pause => {
+                return ApartmentOfDay::LastApartment(last_appartment, holiday);
+            }
+            // NOTE: the day belongs to whoever would wash on it, absent apartments are passed over
+            HolidayPolicy::LoseDay => {
+                let turn = create_rotated_apartment(date, last_appartment, config);
+                return ApartmentOfDay::LastApartment(turn.extract_appartment().clone(), holiday);
+            }
+            HolidayPolicy::Shift => {
+                let turn = create_rotated_apartment(date, last_appartment, config);
+                if let ApartmentOfDay::CurrentApartment(apartment) = &turn {
+                    moved.push(apartment.id());
+                }
+                return ApartmentOfDay::LastApartment(turn.extract_appartment().clone(), holiday);
             }
+            HolidayPolicy::Keep => {}
         }
     }
+    // keep a stale value if the current day is not used
+    if let Some(blackout_name) = config.blackout(date) {
+        return ApartmentOfDay::LastApartment(
+            last_appartment,
+            SkipReason::Blackout(blackout_name.to_owned()),
+        );
+    }
+    let weekday = plan::Weekday::from(date.weekday());
+    if (exclude_sunday && weekday == plan::Weekday::Sunday)
+        || config.excluded_weekdays.contains(&weekday)
+    {
+        return ApartmentOfDay::LastApartment(
+            last_appartment,
+            SkipReason::ExcludedWeekday(weekday),
+        );
+    }
+
+    // a moved day comes before the rotation, which waits until the day is made up
+    if !moved.is_empty() {
+        let id = moved.remove(0);
+        if !config.is_absent(id, date) {
+            return ApartmentOfDay::MovedDay(last_appartment, id);
+        }
+        // NOTE: an absent apartment loses its moved day like any other day
+        if config.vacancy_policy == VacancyPolicy::LeaveFree {
+            return ApartmentOfDay::LastApartment(last_appartment, SkipReason::Vacancy(id));
+        }
+    }
+    create_rotated_apartment(date, last_appartment, config)
 }
 
-fn print_local_weekday(date: LocalDate) -> String {
-    let weekday = match date.weekday() {
-        Weekday::Sunday => "So",
-        Weekday::Monday => "Mo",
-        Weekday::Tuesday => "Di",
-        Weekday::Wednesday => "Mi",
-        Weekday::Thursday => "Do",
-        Weekday::Friday => "Fr",
-        Weekday::Saturday => "Sa",
-    };
-    weekday.to_string()
+// @return the next apartment in the rotation on a day on which the laundry room is open
+fn create_rotated_apartment(
+    date: LocalDate,
+    last_appartment: Apartment,
+    config: &Config,
+) -> ApartmentOfDay {
+    // go to the next appartment
+    let next = last_appartment.next(config);
+    if !config.is_absent(next.id(), date) {
+        return ApartmentOfDay::CurrentApartment(next);
+    }
+    let vacancy = SkipReason::Vacancy(next.id());
+    match config.vacancy_policy {
+        VacancyPolicy::PassOn => match next_present_apartment(date, &next, config) {
+            Some(present) => ApartmentOfDay::CurrentApartment(present),
+            None => ApartmentOfDay::LastApartment(next, vacancy),
+        },
+        // NOTE: the rotation moves on although nobody washes
+        VacancyPolicy::LeaveFree => ApartmentOfDay::LastApartment(next, vacancy),
+    }
 }
 
-pub fn month_to_string(month: Month) -> String {
-    match month {
-        datetime::Month::January => "Januar".to_owned(),
-        datetime::Month::February => "Februar".to_owned(),
-        datetime::Month::March => "März".to_owned(),
-        datetime::Month::April => "April".to_owned(),
-        datetime::Month::May => "Mai".to_owned(),
-        datetime::Month::June => "Juni".to_owned(),
-        datetime::Month::July => "Juli".to_owned(),
-        datetime::Month::August => "August".to_owned(),
-        datetime::Month::September => "September".to_owned(),
-        datetime::Month::October => "Oktober".to_owned(),
-        datetime::Month::November => "November".to_owned(),
-        datetime::Month::December => "Dezember".to_owned(),
+// @param date_str date in the format produced by `print_local_date` plus the year, e.g. 24.12.2025
+pub fn string_to_local_date(date_str: &str) -> Option<LocalDate> {
+    let ymd: Vec<i64> = date_str
+        .split('.')
+        .filter_map(|char| char.parse().ok())
+        .collect();
+    match ymd.as_slice() {
+        [day, month_num, year] => datetime::Month::from_one(*month_num as i8)
+            .and_then(|month| LocalDate::ymd(*year, month, *day as i8))
+            .ok(),
+        _ => None,
     }
 }
 
-fn print_local_date(date: LocalDate) -> String {
+// inverse of `string_to_local_date`
+pub fn print_full_local_date(date: LocalDate) -> String {
+    format!("{}.{}", print_local_date(date), date.year())
+}
+
+pub fn print_local_date(date: LocalDate) -> String {
     let day = date.day().to_string();
     let month = match date.month() {
         datetime::Month::January => "1",
@@ -896,40 +1337,621 @@ fn print_local_date(date: LocalDate) -> String {
     [day, month.to_owned()].join(".")
 }
 
-// @key months from january
-// @value vector of appartment texts
-#[derive(Debug)]
-pub struct YearMap(pub HashMap<i8, Vec<DayHTMLData>>);
+// @return the following day, also across the turn of the year
+pub fn next_date(date: LocalDate) -> LocalDate {
+    // NOTE: `LocalDate::yd` only goes up to yearday 366, which is too short after a leap year
+    if date.month() == Month::December && date.day() == 31 {
+        // NOTE: safe b/c every year has a first day
+        return LocalDate::ymd(date.year() + 1, Month::January, 1).unwrap();
+    }
+    // NOTE: safe b/c any other day is followed by a day of the same year
+    LocalDate::yd(date.year(), date.yearday() as i64 + 1).unwrap()
+}
 
-pub fn create_full_year(
+// @return every day from start to end in order
+fn create_days(
     config: &Config,
-    year: u16,
+    start: LocalDate,
+    end: LocalDate,
     last_appartment: Apartment,
+    moved: Vec<plan::ApartmentId>,
     exclude_sunday: bool,
     holidays: &BTreeMap<LocalDate, String>,
-) -> YearMap {
-    let mut year_map = HashMap::new();
+) -> Vec<Day> {
     let mut current_day = Day::new(
-        year,
+        start,
         last_appartment,
-        &config.position_map,
+        config,
         exclude_sunday,
         holidays,
+        moved,
     );
+    let mut days = vec![];
+    while current_day.date < end {
+        let next_day = current_day.next(config, exclude_sunday, holidays);
+        days.push(current_day);
+        current_day = next_day;
+    }
+    days.push(current_day);
+    days
+}
 
-    year_map.insert(
-        current_day.date.month().months_from_january() as i8,
-        vec![current_day.create_html_data()],
+// @param start first day of the plan
+// @param end last day of the plan, may be in a later year than start
+// @param apartment_info state at the end of the previous plan, the rotation continues after it
+pub fn create_plan(
+    config: &Config,
+    start: LocalDate,
+    end: LocalDate,
+    apartment_info: &ApartmentInfo,
+    exclude_sunday: bool,
+    holidays: &BTreeMap<LocalDate, String>,
+) -> Result<Plan, ValidationError> {
+    if end < start {
+        return Err(ValidationError::InvalidPeriod(start, end));
+    }
+    // NOTE: the renderers rely on a valid layout
+    if !config.layout.is_valid() {
+        return Err(ValidationError::InvalidMonthsPerRow(
+            config.layout.months_per_row,
+        ));
+    }
+    // NOTE: the rotation relies on a sequence with every apartment at most once
+    if !config.is_rotation_order_valid() {
+        return Err(ValidationError::InvalidRotationOrder);
+    }
+    let last_appartment = Apartment::new(config, apartment_info, &config.position_map)?;
+    let days = create_days(
+        config,
+        start,
+        end,
+        last_appartment.clone(),
+        apartment_info.moved_days.clone(),
+        exclude_sunday,
+        holidays,
     );
+    // NOTE: safe b/c there is always at least the first day
+    let last_day = days.last().unwrap();
+    // NOTE: days moved by a holiday at the end of the plan are made up in the next one
+    let last_apartment = ApartmentInfo {
+        moved_days: last_day.moved.clone(),
+        ..last_day.last_appartment().info(&config.position_map)
+    };
+    let mut plan = Plan {
+        start,
+        end,
+        days: days.iter().map(Day::create_plan_day).collect(),
+        last_apartment,
+    };
+    if let Scheduler::Fair(fairness) = &config.scheduler {
+        fairness::balance(
+            config,
+            fairness,
+            &mut plan,
+            &last_appartment,
+            &apartment_info.moved_days,
+        );
+    }
+    Ok(plan)
+}
 
-    while let Ok(valid_day) = current_day.next(&config.position_map, exclude_sunday, holidays) {
-        let current_month = valid_day.date.month().months_from_january() as i8;
-        if let Some(vector) = year_map.get_mut(&current_month) {
-            vector.push(valid_day.create_html_data());
-        } else {
-            year_map.insert(current_month, vec![valid_day.create_html_data()]);
-        }
-        current_day = valid_day;
+// @return first and last day of the year
+pub fn full_year(year: u16) -> (LocalDate, LocalDate) {
+    // NOTE: safe b/c every year has a first and a last day
+    let start = LocalDate::ymd(year as i64, Month::January, 1).unwrap();
+    let end = LocalDate::ymd(year as i64, Month::December, 31).unwrap();
+    (start, end)
+}
+
+pub fn create_full_year(
+    config: &Config,
+    year: u16,
+    apartment_info: &ApartmentInfo,
+    exclude_sunday: bool,
+    holidays: &BTreeMap<LocalDate, String>,
+) -> Result<Plan, ValidationError> {
+    let (start, end) = full_year(year);
+    create_plan(config, start, end, apartment_info, exclude_sunday, holidays)
+}
+
+// @return the config of the sample building used by the tests
+#[cfg(test)]
+pub(crate) fn sample_config() -> Config {
+    serde_json::from_str(include_str!("../resources/sample_config.json")).unwrap()
+}
+
+// @return the state before the first turn, the rotation starts with the first floor
+#[cfg(test)]
+pub(crate) fn sample_state() -> ApartmentInfo {
+    ApartmentInfo {
+        current_floor: 0,
+        position: FloorPosition::Left,
+        days_left: 0,
+        moved_days: vec![],
     }
-    YearMap(year_map)
+}
+
+// @return the plan of a whole year without holidays and Sundays
+#[cfg(test)]
+pub(crate) fn sample_plan(config: &Config, year: u16) -> Plan {
+    create_full_year(config, year, &sample_state(), true, &BTreeMap::new()).unwrap()
+}
+
+#[test]
+pub fn rotate_through_floor_with_many_apartments() {
+    let units = ["A", "B", "C", "D", "E"]
+        .iter()
+        .map(|name| ApartmentUnitInfo {
+            name: name.to_string(),
+            days_total: 1,
+        })
+        .collect();
+    let mut config = sample_config();
+    config.position_map = HashMap::from([
+        (0, FloorInfo::Apartments(units)),
+        (
+            1,
+            FloorInfo::OneApartment(SingleApartmentFloorInfo { days_total: 2 }),
+        ),
+    ]);
+    validate_config(&config).unwrap();
+    let apartment_info = ApartmentInfo {
+        current_floor: 1,
+        position: FloorPosition::Left,
+        days_left: 0,
+        moved_days: vec![],
+    };
+    let mut apartment = Apartment::new(&config, &apartment_info, &config.position_map).unwrap();
+    let mut printed = vec![];
+    for _ in 0..8 {
+        apartment = apartment.next(&config);
+        printed.push(apartment.print(&config));
+    }
+    assert_eq!(
+        printed,
+        ["P. A", "P. B", "P. C", "P. D", "P. E", "1", "1", "P. A"]
+    );
+}
+
+#[test]
+pub fn carry_rotation_across_years() {
+    let mut config = sample_config();
+    config.position_map = HashMap::from([
+        (
+            0,
+            FloorInfo::OneApartment(SingleApartmentFloorInfo { days_total: 1 }),
+        ),
+        (
+            1,
+            FloorInfo::OneApartment(SingleApartmentFloorInfo { days_total: 1 }),
+        ),
+    ]);
+    let apartment_info = ApartmentInfo {
+        current_floor: 1,
+        position: FloorPosition::Left,
+        days_left: 0,
+        moved_days: vec![],
+    };
+    let start = LocalDate::ymd(2025, Month::December, 30).unwrap();
+    let end = LocalDate::ymd(2026, Month::January, 2).unwrap();
+    let plan = create_plan(
+        &config,
+        start,
+        end,
+        &apartment_info,
+        false,
+        &BTreeMap::new(),
+    )
+    .unwrap();
+    let floors: Vec<u32> = plan
+        .days
+        .iter()
+        .filter_map(|day| day.apartment(0))
+        .map(|apartment| apartment.floor)
+        .collect();
+    assert_eq!(floors, [0, 1, 0, 1]);
+    assert_eq!(plan.months().len(), 2);
+}
+
+#[test]
+pub fn continue_plan_from_last_apartment() {
+    let config = sample_config();
+    let apartment_info = ApartmentInfo {
+        current_floor: 1,
+        position: FloorPosition::Left,
+        days_left: 0,
+        moved_days: vec![],
+    };
+    let holidays = BTreeMap::new();
+    let (start, _) = full_year(2025);
+    let (_, end) = full_year(2026);
+    let both_years = create_plan(&config, start, end, &apartment_info, true, &holidays).unwrap();
+    let first_year = create_full_year(&config, 2025, &apartment_info, true, &holidays).unwrap();
+    let second_year =
+        create_full_year(&config, 2026, &first_year.last_apartment, true, &holidays).unwrap();
+
+    let assignments = |plan: &Plan| -> Vec<plan::Assignment> {
+        plan.days
+            .iter()
+            .map(|day| day.assignments[0].clone())
+            .collect()
+    };
+    assert_eq!(assignments(&second_year), assignments(&both_years)[365..]);
+    assert_eq!(second_year.last_apartment, both_years.last_apartment);
+}
+
+#[test]
+pub fn skip_absent_apartments() {
+    let mut config = sample_config();
+    config.apartments = serde_json::from_str(
+        r#"[{"floor": 1, "position": "Left", "vacant": true},
+            {"floor": 1, "position": "Middle",
+             "absences": [{"start": "2025-01-01", "end": "2025-01-31"}]}]"#,
+    )
+    .unwrap();
+    validate_config(&config).unwrap();
+    let (start, end) = full_year(2025);
+    let create = |config: &Config| {
+        create_plan(config, start, end, &sample_state(), false, &BTreeMap::new()).unwrap()
+    };
+    let floor_one = |unit| Some(plan::ApartmentId { floor: 1, unit });
+
+    let plan = create(&config);
+    assert_eq!(plan.days[0].apartment(0), floor_one(2));
+    assert!(plan.days.iter().all(|day| day.apartment(0) != floor_one(0)));
+    let middle_days: Vec<&PlanDay> = plan
+        .days
+        .iter()
+        .filter(|day| day.apartment(0) == floor_one(1))
+        .collect();
+    assert_eq!(middle_days[0].date.month(), Month::February);
+
+    config.vacancy_policy = VacancyPolicy::LeaveFree;
+    let plan = create(&config);
+    assert_eq!(
+        plan.days[0].assignments[0],
+        plan::Assignment::Skipped(SkipReason::Vacancy(plan::ApartmentId { floor: 1, unit: 0 }))
+    );
+    assert_eq!(plan.days[2].apartment(0), None);
+    assert_eq!(plan.days[3].apartment(0), floor_one(2));
+}
+
+#[test]
+pub fn match_recurring_blackouts() {
+    let first_monday = BlackoutRule::Monthly {
+        week: WeekOfMonth::First,
+        weekday: plan::Weekday::Monday,
+    };
+    let last_friday = BlackoutRule::Monthly {
+        week: WeekOfMonth::Last,
+        weekday: plan::Weekday::Friday,
+    };
+    let cleaning = BlackoutRule::Yearly { month: 4, day: 15 };
+    let date = |month, day| LocalDate::ymd(2025, month, day).unwrap();
+
+    assert!(first_monday.matches(date(Month::September, 1)));
+    assert!(!first_monday.matches(date(Month::September, 8)));
+    assert!(last_friday.matches(date(Month::December, 26)));
+    assert!(!last_friday.matches(date(Month::December, 19)));
+    assert!(cleaning.matches(date(Month::April, 15)));
+    assert!(!BlackoutRule::Yearly { month: 2, day: 30 }.is_valid());
+}
+
+#[test]
+pub fn apply_holiday_policies() {
+    let mut config = sample_config();
+    let start = LocalDate::ymd(2025, Month::January, 1).unwrap();
+    let end = LocalDate::ymd(2025, Month::January, 4).unwrap();
+    let holidays = BTreeMap::from([(next_date(start), "Berchtoldstag".to_string())]);
+    let create = |config: &mut Config, holiday_policy| {
+        config.holiday_policy = holiday_policy;
+        create_plan(config, start, end, &sample_state(), false, &holidays)
+            .unwrap()
+            .days
+            .iter()
+            .map(|day| day.apartment(0).map(|apartment| apartment.unit))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        create(&mut config, HolidayPolicy::Pause),
+        [Some(0), None, Some(0), Some(1)]
+    );
+    assert_eq!(
+        create(&mut config, HolidayPolicy::LoseDay),
+        [Some(0), None, Some(1), Some(2)]
+    );
+    assert_eq!(
+        create(&mut config, HolidayPolicy::Keep),
+        [Some(0), Some(0), Some(1), Some(2)]
+    );
+    assert_eq!(
+        create(&mut config, HolidayPolicy::Shift),
+        [Some(0), None, Some(0), Some(1)]
+    );
+    // the left apartment is away on its moved day and passes it on
+    config.apartments = serde_json::from_str(
+        r#"[{"floor": 1, "position": "Left",
+             "absences": [{"start": "2025-01-03", "end": "2025-01-03"}]}]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        create(&mut config, HolidayPolicy::Shift),
+        [Some(0), None, Some(1), Some(2)]
+    );
+    // the left apartment is away on the holiday, the day belongs to the middle one
+    config.apartments = serde_json::from_str(
+        r#"[{"floor": 1, "position": "Left",
+             "absences": [{"start": "2025-01-02", "end": "2025-01-02"}]}]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        create(&mut config, HolidayPolicy::Pause),
+        [Some(0), None, Some(0), Some(1)]
+    );
+    assert_eq!(
+        create(&mut config, HolidayPolicy::Shift),
+        [Some(0), None, Some(1), Some(2)]
+    );
+}
+
+#[test]
+pub fn make_up_moved_days_in_next_plan() {
+    let mut config = sample_config();
+    config.holiday_policy = HolidayPolicy::Shift;
+    let date = |day| LocalDate::ymd(2025, Month::January, day).unwrap();
+    let holidays = BTreeMap::from([(date(2), "Berchtoldstag".to_string())]);
+    let units = |plan: &Plan| -> Vec<Option<usize>> {
+        plan.days
+            .iter()
+            .map(|day| day.apartment(0).map(|apartment| apartment.unit))
+            .collect()
+    };
+
+    let both = create_plan(&config, date(1), date(4), &sample_state(), false, &holidays).unwrap();
+    let first = create_plan(&config, date(1), date(2), &sample_state(), false, &holidays).unwrap();
+    assert_eq!(
+        first.last_apartment.moved_days,
+        [plan::ApartmentId { floor: 1, unit: 0 }]
+    );
+    let state = serde_json::to_string(&first.last_apartment).unwrap();
+    let state: ApartmentInfo = serde_json::from_str(&state).unwrap();
+    let second = create_plan(&config, date(3), date(4), &state, false, &holidays).unwrap();
+    assert_eq!(units(&second), units(&both)[2..]);
+    assert!(second.last_apartment.moved_days.is_empty());
+
+    let unknown = ApartmentInfo {
+        moved_days: vec![plan::ApartmentId { floor: 1, unit: 3 }],
+        ..sample_state()
+    };
+    assert!(create_plan(&config, date(3), date(4), &unknown, false, &holidays).is_err());
+}
+
+#[test]
+pub fn fill_slots_in_order() {
+    let mut config = sample_config();
+    config.slots = vec!["Vormittag".to_string(), "Nachmittag".to_string()];
+    let start = LocalDate::ymd(2025, Month::January, 1).unwrap();
+    let end = LocalDate::ymd(2025, Month::January, 3).unwrap();
+    let holidays = BTreeMap::from([(next_date(start), "Berchtoldstag".to_string())]);
+    let plan = create_plan(&config, start, end, &sample_state(), false, &holidays).unwrap();
+    let apartments: Vec<Vec<Option<(u32, usize)>>> = plan
+        .days
+        .iter()
+        .map(|day| {
+            (0..config.slot_count())
+                .map(|slot| {
+                    day.apartment(slot)
+                        .map(|apartment| (apartment.floor, apartment.unit))
+                })
+                .collect()
+        })
+        .collect();
+
+    // the left apartment of the first floor washes both slots of its two days
+    assert_eq!(
+        apartments,
+        [
+            [Some((1, 0)), Some((1, 0))],
+            [None, None],
+            [Some((1, 1)), Some((1, 2))]
+        ]
+    );
+    assert!(plan.days[1].is_closed());
+    assert_eq!(plan.days[2].apartments().len(), 2);
+}
+
+#[test]
+pub fn follow_rotation_orders() {
+    let mut config = sample_config();
+    let sequence = |config: &Config| -> Vec<(u32, usize)> {
+        config
+            .rotation_sequence()
+            .iter()
+            .map(|apartment| (apartment.floor, apartment.unit))
+            .collect()
+    };
+
+    assert_eq!(
+        sequence(&config),
+        [
+            (0, 0),
+            (1, 0),
+            (1, 1),
+            (1, 2),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (3, 0),
+            (3, 1)
+        ]
+    );
+    config.rotation_order = RotationOrder::TopDown;
+    assert_eq!(
+        sequence(&config),
+        [
+            (3, 0),
+            (3, 1),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (1, 0),
+            (1, 1),
+            (1, 2),
+            (0, 0)
+        ]
+    );
+    config.rotation_order = RotationOrder::Serpentine;
+    assert_eq!(
+        sequence(&config),
+        [
+            (0, 0),
+            (1, 2),
+            (1, 1),
+            (1, 0),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (3, 1),
+            (3, 0)
+        ]
+    );
+
+    config.rotation_order = RotationOrder::Custom(vec![
+        ApartmentPosition {
+            floor: 3,
+            position: FloorPosition::Right,
+        },
+        ApartmentPosition {
+            floor: 1,
+            position: FloorPosition::Middle,
+        },
+    ]);
+    let start = LocalDate::ymd(2025, Month::January, 1).unwrap();
+    let end = LocalDate::ymd(2025, Month::January, 5).unwrap();
+    let plan = create_plan(
+        &config,
+        start,
+        end,
+        &sample_state(),
+        false,
+        &BTreeMap::new(),
+    )
+    .unwrap();
+    // the apartment to start from isn't listed, the rotation begins with the first listed one
+    assert_eq!(
+        plan.days
+            .iter()
+            .map(|day| day
+                .apartment(0)
+                .map(|apartment| (apartment.floor, apartment.unit)))
+            .collect::<Vec<_>>(),
+        [
+            Some((3, 1)),
+            Some((3, 1)),
+            Some((1, 1)),
+            Some((3, 1)),
+            Some((3, 1))
+        ]
+    );
+
+    config.rotation_order = RotationOrder::Custom(vec![]);
+    assert!(!config.is_rotation_order_valid());
+}
+
+#[test]
+pub fn order_labelled_floors() {
+    let mut config: Config = serde_json::from_str(
+        r#"{
+            "title": "House 2",
+            "position_map": {
+                "9": { "OneApartment": { "days_total": 1 } },
+                "1": { "TwoApartments": { "left_days_total": 1, "right_days_total": 1 } },
+                "5": { "TwoApartments": { "left_days_total": 1, "right_days_total": 1 } }
+            },
+            "floors": [
+                { "floor": 9, "label": "UG" },
+                { "floor": 0, "label": "EG" },
+                { "floor": 1 },
+                { "floor": 5, "label": "DG" }
+            ]
+        }"#,
+    )
+    .unwrap();
+    assert!(validate_config(&config).is_ok());
+    assert_eq!(config.floor_order(), [9, 1, 5]);
+    let printed: Vec<String> = config
+        .apartment_ids()
+        .iter()
+        .map(|apartment| apartment.print(&config))
+        .collect();
+    assert_eq!(
+        printed,
+        ["UG", "1. Links", "1. Rechts", "DG Links", "DG Rechts"]
+    );
+
+    config.rotation_order = RotationOrder::TopDown;
+    assert_eq!(config.rotation_sequence()[0].floor, 5);
+    config.rotation_order = RotationOrder::Serpentine;
+    assert_eq!(
+        config
+            .rotation_sequence()
+            .iter()
+            .map(|apartment| (apartment.floor, apartment.unit))
+            .collect::<Vec<_>>(),
+        [(9, 0), (1, 1), (1, 0), (5, 0), (5, 1)]
+    );
+
+    config.floors.retain(|label| label.floor != 1);
+    assert!(matches!(
+        validate_config(&config),
+        Err(ValidationError::UnlistedFloor(1))
+    ));
+    config.floors.clear();
+    assert_eq!(config.floor_order(), [1, 5, 9]);
+    config.floors.push(FloorLabel {
+        floor: 9,
+        label: None,
+    });
+    config.floors.push(FloorLabel {
+        floor: 9,
+        label: Some("Keller".to_string()),
+    });
+    assert!(matches!(
+        validate_config(&config),
+        Err(ValidationError::DuplicateFloor(9))
+    ));
+}
+
+#[test]
+pub fn reject_floor_without_apartments() {
+    let mut config = sample_config();
+    config.position_map.insert(4, FloorInfo::Apartments(vec![]));
+
+    assert_eq!(
+        config.position_map[&4].unit_index(&FloorPosition::Right),
+        None
+    );
+    assert!(matches!(
+        validate_config(&config),
+        Err(ValidationError::EmptyFloor(4))
+    ));
+}
+
+#[test]
+pub fn cross_end_of_leap_year() {
+    let config = sample_config();
+    let start = LocalDate::ymd(2028, Month::April, 1).unwrap();
+    let end = LocalDate::ymd(2029, Month::March, 31).unwrap();
+    let plan = create_plan(&config, start, end, &sample_state(), true, &BTreeMap::new()).unwrap();
+
+    assert_eq!(plan.days.len(), 365);
+    assert_eq!(
+        next_date(LocalDate::ymd(2028, Month::December, 31).unwrap()),
+        LocalDate::ymd(2029, Month::January, 1).unwrap()
+    );
+    assert_eq!(
+        next_date(LocalDate::ymd(2028, Month::February, 28).unwrap()),
+        LocalDate::ymd(2028, Month::February, 29).unwrap()
+    );
 }
@@ -0,0 +1,377 @@
+use crate::holiday_cache::{self, HolidayCache};
+use crate::holidays::{self, HolidayError, HolidaySource};
+use crate::html::{self, TemplateError};
+use crate::plan;
+use crate::types::{self, ValidationError};
+use datetime::{DatePiece, LocalDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::{error::Error, fmt};
+
+const CONFIG_FILE: &str = "config.json";
+const BUILDING_FILE: &str = "building.json";
+const PLAN_DIR: &str = "plans";
+
+// A directory with one subdirectory per building, each holds the config as written by
+// `types::save_config`, the building file and the generated plans:
+// <name>/config.json, <name>/building.json, <name>/plans/<start>_<end>.html
+pub struct Workspace {
+    dir: PathBuf,
+}
+
+// where the holidays of a building are taken from
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct HolidayRegion {
+    // ISO 3166-1, e.g. CH
+    pub country: String,
+    // ISO 3166-2, e.g. CH-ZH, empty for the holidays of the whole country
+    pub subdivision: String,
+    #[serde(default)]
+    pub source: HolidaySource,
+}
+
+// a plan generated for a building
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PlanRecord {
+    // seconds since the unix epoch
+    pub generated_at: u64,
+    // ISO 8601, e.g. 2025-01-01
+    pub start: String,
+    pub end: String,
+    // washed last before the plan
+    pub first_state: types::ApartmentInfo,
+    pub last_state: types::ApartmentInfo,
+    // HTML plan relative to the directory of the building
+    pub file: String,
+}
+
+// everything the workspace keeps about a building besides its config
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Building {
+    // no holidays if None
+    pub region: Option<HolidayRegion>,
+    #[serde(default)]
+    pub exclude_sunday: bool,
+    // washed last in the latest plan, the next plan continues from here
+    pub state: types::ApartmentInfo,
+    // oldest plan first
+    #[serde(default)]
+    pub history: Vec<PlanRecord>,
+}
+
+#[derive(Debug)]
+pub enum WorkspaceError {
+    Io(std::io::Error),
+    Format(serde_json::Error),
+    Config(ValidationError),
+    Holidays(HolidayError),
+    Template(TemplateError),
+    // @param name of the building
+    InvalidName(String),
+    AlreadyExists(String),
+    NotFound(String),
+    // @param start and end of an earlier plan the new one overlaps
+    OverlappingPlan(String, String),
+}
+
+impl Error for WorkspaceError {}
+
+impl fmt::Display for WorkspaceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => fmt::Display::fmt(err, f),
+            Self::Format(err) => fmt::Display::fmt(err, f),
+            Self::Config(err) => fmt::Display::fmt(err, f),
+            Self::Holidays(err) => fmt::Display::fmt(err, f),
+            Self::Template(err) => fmt::Display::fmt(err, f),
+            Self::InvalidName(name) => write!(
+                f,
+                "'{}' can't be the name of a building, use a name without slashes.",
+                name
+            ),
+            Self::AlreadyExists(name) => write!(f, "The building '{}' already exists.", name),
+            Self::NotFound(name) => write!(f, "There is no building '{}'.", name),
+            Self::OverlappingPlan(start, end) => write!(
+                f,
+                "There already is a plan from {} to {}, only the latest plan can be generated again.",
+                start, end
+            ),
+        }
+    }
+}
+
+impl From<std::io::Error> for WorkspaceError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for WorkspaceError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Format(err)
+    }
+}
+
+// NOTE: the name is a directory, it must not leave the workspace
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\'])
+}
+
+impl Workspace {
+    pub fn new(dir: PathBuf) -> Workspace {
+        Workspace { dir }
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf, WorkspaceError> {
+        if !is_valid_name(name) {
+            return Err(WorkspaceError::InvalidName(name.to_owned()));
+        }
+        Ok(self.dir.join(name))
+    }
+
+    // @return the path of an existing building
+    fn existing_path(&self, name: &str) -> Result<PathBuf, WorkspaceError> {
+        let path = self.path(name)?;
+        if !path.join(BUILDING_FILE).is_file() {
+            return Err(WorkspaceError::NotFound(name.to_owned()));
+        }
+        Ok(path)
+    }
+
+    // @return the names of all buildings in alphabetical order
+    pub fn list(&self) -> Result<Vec<String>, WorkspaceError> {
+        if !self.dir.is_dir() {
+            return Ok(vec![]);
+        }
+        let mut names: Vec<String> = std::fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join(BUILDING_FILE).is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    pub fn read_config(&self, name: &str) -> Result<types::Config, WorkspaceError> {
+        types::config_from_file(self.existing_path(name)?.join(CONFIG_FILE))
+            .map_err(WorkspaceError::Config)
+    }
+
+    pub fn read_building(&self, name: &str) -> Result<Building, WorkspaceError> {
+        let content = std::fs::read_to_string(self.existing_path(name)?.join(BUILDING_FILE))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn write_building(&self, name: &str, building: &Building) -> Result<(), WorkspaceError> {
+        let content = serde_json::to_string_pretty(building)?;
+        std::fs::write(self.path(name)?.join(BUILDING_FILE), content)?;
+        Ok(())
+    }
+
+    pub fn create(
+        &self,
+        name: &str,
+        config: types::Config,
+        building: &Building,
+    ) -> Result<(), WorkspaceError> {
+        let path = self.path(name)?;
+        if path.exists() {
+            return Err(WorkspaceError::AlreadyExists(name.to_owned()));
+        }
+        // NOTE: an invalid config couldn't be read again
+        types::validate_config(&config).map_err(WorkspaceError::Config)?;
+        building
+            .state
+            .validate(&config)
+            .map_err(WorkspaceError::Config)?;
+        std::fs::create_dir_all(path.join(PLAN_DIR))?;
+        types::save_config(config, path.join(CONFIG_FILE))?;
+        self.write_building(name, building)
+    }
+
+    // @return the copy, it continues from the same state but has no plans yet
+    pub fn duplicate(&self, name: &str, new_name: &str) -> Result<Building, WorkspaceError> {
+        let config = self.read_config(name)?;
+        let building = Building {
+            history: vec![],
+            ..self.read_building(name)?
+        };
+        self.create(new_name, config, &building)?;
+        Ok(building)
+    }
+
+    // Writes the HTML plan into the plans of the building and continues its state from the plan.
+    // A period starting with the latest plan replaces it, other periods must not overlap any plan.
+    // NOTE: every plan advances the state, the periods of a building are generated in order
+    pub fn generate(
+        &self,
+        name: &str,
+        start: LocalDate,
+        end: LocalDate,
+        holidays: &BTreeMap<LocalDate, String>,
+    ) -> Result<PlanRecord, WorkspaceError> {
+        let path = self.existing_path(name)?;
+        let config = self.read_config(name)?;
+        let mut building = self.read_building(name)?;
+        let start_iso = plan::print_iso_date(start);
+        let end_iso = plan::print_iso_date(end);
+        // generating the latest plan again starts from the same state
+        let replaced = match building.history.last() {
+            Some(record) if record.start == start_iso => building.history.pop(),
+            _ => None,
+        };
+        if let Some(record) = &replaced {
+            building.state = record.first_state.clone();
+        }
+        // NOTE: ISO dates compare in the order of the days
+        if let Some(record) = building
+            .history
+            .iter()
+            .find(|record| record.start <= end_iso && start_iso <= record.end)
+        {
+            return Err(WorkspaceError::OverlappingPlan(
+                record.start.clone(),
+                record.end.clone(),
+            ));
+        }
+        let plan = types::create_plan(
+            &config,
+            start,
+            end,
+            &building.state,
+            building.exclude_sunday,
+            holidays,
+        )
+        .map_err(WorkspaceError::Config)?;
+        let content = html::create_year_html(&config, &plan).map_err(WorkspaceError::Template)?;
+        let file = format!("{}/{}_{}.html", PLAN_DIR, start_iso, end_iso);
+        std::fs::create_dir_all(path.join(PLAN_DIR))?;
+        std::fs::write(path.join(&file), content)?;
+        if let Some(record) = replaced.filter(|record| record.file != file) {
+            // NOTE: the plan may already be gone, the new one replaces it anyway
+            std::fs::remove_file(path.join(record.file)).ok();
+        }
+
+        let record = PlanRecord {
+            generated_at: holiday_cache::now(),
+            start: start_iso,
+            end: end_iso,
+            first_state: building.state.clone(),
+            last_state: plan.last_apartment.clone(),
+            file,
+        };
+        building.state = plan.last_apartment;
+        building.history.push(record.clone());
+        self.write_building(name, &building)?;
+        Ok(record)
+    }
+
+    async fn load_holidays(
+        &self,
+        name: &str,
+        start: LocalDate,
+        end: LocalDate,
+        cache: Option<&HolidayCache>,
+    ) -> Result<BTreeMap<LocalDate, String>, WorkspaceError> {
+        let Some(region) = self.read_building(name)?.region else {
+            return Ok(BTreeMap::new());
+        };
+        let config = self.read_config(name)?;
+        holidays::load_holidays_for_years(
+            start.year() as u16,
+            end.year() as u16,
+            region.country,
+            region.subdivision,
+            config.locale,
+            region.source,
+            cache,
+            false,
+        )
+        .await
+        .map_err(WorkspaceError::Holidays)
+    }
+
+    // Generates the plan of every building with the holidays of its region.
+    // @return the plan or the error of every building, a failing building doesn't stop the others
+    pub async fn generate_all(
+        &self,
+        start: LocalDate,
+        end: LocalDate,
+        cache: Option<&HolidayCache>,
+    ) -> Result<Vec<(String, Result<PlanRecord, WorkspaceError>)>, WorkspaceError> {
+        let mut results = vec![];
+        for name in self.list()? {
+            let record = match self.load_holidays(&name, start, end, cache).await {
+                Ok(holidays) => self.generate(&name, start, end, &holidays),
+                Err(err) => Err(err),
+            };
+            results.push((name, record));
+        }
+        Ok(results)
+    }
+}
+
+#[test]
+pub fn manage_buildings() {
+    let dir = std::env::temp_dir().join(format!("waschplan_workspace_{}", std::process::id()));
+    let workspace = Workspace::new(dir.clone());
+    let config = types::sample_config();
+    let building = Building {
+        region: None,
+        exclude_sunday: false,
+        state: types::sample_state(),
+        history: vec![],
+    };
+
+    let mut invalid = types::sample_config();
+    invalid.layout.months_per_row = 5;
+    assert!(matches!(
+        workspace.create("Haus C", invalid, &building),
+        Err(WorkspaceError::Config(_))
+    ));
+    workspace.create("Haus A", config, &building).unwrap();
+    workspace.duplicate("Haus A", "Haus B").unwrap();
+    assert_eq!(workspace.list().unwrap(), ["Haus A", "Haus B"]);
+    assert!(matches!(
+        workspace.duplicate("Haus A", "Haus B"),
+        Err(WorkspaceError::AlreadyExists(_))
+    ));
+    assert!(matches!(
+        workspace.read_building("../Haus A"),
+        Err(WorkspaceError::InvalidName(_))
+    ));
+
+    let (start, end) = types::full_year(2025);
+    let record = workspace
+        .generate("Haus A", start, end, &BTreeMap::new())
+        .unwrap();
+    let updated = workspace.read_building("Haus A").unwrap();
+    assert!(dir.join("Haus A").join(&record.file).is_file());
+    assert_eq!(updated.history, std::slice::from_ref(&record));
+    assert_eq!(updated.state, record.last_state);
+
+    // the same period again replaces the plan
+    let again = workspace
+        .generate("Haus A", start, end, &BTreeMap::new())
+        .unwrap();
+    let updated = workspace.read_building("Haus A").unwrap();
+    assert_eq!(updated.history.len(), 1);
+    assert_eq!(again.first_state, record.first_state);
+    assert_eq!(again.last_state, record.last_state);
+    let (next_start, next_end) = types::full_year(2026);
+    assert!(matches!(
+        workspace.generate("Haus A", end, next_end, &BTreeMap::new()),
+        Err(WorkspaceError::OverlappingPlan(_, _))
+    ));
+    let next = workspace
+        .generate("Haus A", next_start, next_end, &BTreeMap::new())
+        .unwrap();
+    assert_eq!(next.first_state, record.last_state);
+    assert!(matches!(
+        workspace.generate("Haus A", start, end, &BTreeMap::new()),
+        Err(WorkspaceError::OverlappingPlan(_, _))
+    ));
+    assert_eq!(workspace.read_building("Haus B").unwrap(), building);
+    std::fs::remove_dir_all(dir).unwrap();
+}
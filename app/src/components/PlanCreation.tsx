@@ -184,11 +184,24 @@ function PlanCreation({
   };
 
   const getAndSetHolidaysFromWeb = async () => {
-    const holidays: [string, String][] = await invoke("get_holidays", {
-      year: Number(year),
-      countryIso: country,
-      subdivisionIso: region,
-    });
+    const holidaysResult: Result<[string, String][], string> =
+      await invokeResult("get_holidays", {
+        year: Number(year),
+        countryIso: country,
+        subdivisionIso: region,
+      });
+
+    if (!holidaysResult.ok) {
+      const retry = confirm(
+        `Feiertage konnten nicht geladen werden: ${holidaysResult.error}\n\n` +
+          "Erneut versuchen? Die Feiertage können auch von Hand erfasst werden.",
+      );
+      if (retry) {
+        await getAndSetHolidaysFromWeb();
+      }
+      return;
+    }
+    const holidays = holidaysResult.value;
 
     setHolidayDates((oldHolidays: [Date, String][]) => {
       const oldHolidayStr: [string, String][] = oldHolidays.map(